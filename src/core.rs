@@ -1,4 +1,8 @@
 pub(crate) mod ant_grid;
+mod clock;
+mod deliveries;
+mod diplomacy;
+mod disease;
 mod grid;
 mod grid_elements;
 mod options;
@@ -8,13 +12,19 @@ mod team_element;
 mod thread_runner;
 
 pub(crate) use crate::core::ant_grid::AntGrid;
-pub(crate) use crate::core::grid::{Coord, Dir, Team};
+pub(crate) use crate::core::clock::Season;
+pub(crate) use crate::core::deliveries::Deliveries;
+pub(crate) use crate::core::diplomacy::{Diplomacy, Relation, Treaty};
+pub(crate) use crate::core::disease::{Infection, Sir};
+pub(crate) use crate::core::grid::{Coord, Dir, Team, TeamConfig, Terrain};
 pub(crate) use crate::core::grid_elements::{
     ant::Ant,
-    dirt::Dirt,
-    food::Food,
+    aphid::Aphid,
+    caste::Caste,
+    food::{Food, FoodKind},
     grid_element::{GridElement, IntoHelper},
     hive::Hive,
+    predator::{Predator, PredatorKind},
 };
-pub(crate) use crate::core::options::Options;
+pub(crate) use crate::core::options::{FoodPattern, Options};
 pub(crate) use crate::core::runner::{BaseRunner, Runner};
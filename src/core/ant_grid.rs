@@ -1,28 +1,65 @@
-use std::{cell::RefCell, collections::VecDeque, fmt::Display, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    rc::Rc,
+};
 
 use multimap::MultiMap;
 use rand::rngs::SmallRng;
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
+use strum::IntoEnumIterator;
 
 use crate::core::grid::Pheromones;
-use crate::core::grid_elements::dirt::DIRT_ELEMENT;
-use crate::core::options::Options;
+use crate::core::options::{FoodPattern, Options};
 use crate::core::team_element::ElementType::Hive;
-use crate::core::{grid::Grid, grid_elements::grid_element::GridElement, Coord, Team};
+use crate::core::{
+    clock::Clock, grid::Grid, grid_elements::grid_element::GridElement, Coord, Deliveries,
+    Diplomacy, Dir, Relation, Sir, Team, Terrain, Treaty,
+};
 
 use super::{
     grid::Export,
-    grid_elements::food::FOOD_ELEMENT,
-    signals::Signal,
+    grid_elements::aphid::Aphid,
+    grid_elements::corpse::Corpse,
+    grid_elements::food::{Food, FoodKind, FOOD_ELEMENT},
+    grid_elements::predator::{Predator, PredatorKind},
+    signals::{Signal, SignalType},
     team_element::{ElementType, TeamElement},
 };
 
+/// Rounds between rebuilds of the food scent field.
+const SCENT_ROUNDS: usize = 10;
+/// Rounds between samples of the disease history.
+const SIR_ROUNDS: usize = 50;
+/// Samples of disease history kept per team.
+const SIR_HISTORY: usize = 1000;
+/// Rounds a cell stays infectious after a sick ant stood on it.
+const CONTAMINATION_ROUNDS: usize = 50;
+/// Deepest water a cell can hold.
+const MAX_WATER: u8 = 8;
+
 pub(crate) struct AntGrid {
     grid: Grid,
     elements: MultiMap<TeamElement, Rc<RefCell<dyn GridElement>>>,
     round_num: usize,
     pub rng: SmallRng,
     pub options: Options,
+    pub team_options: HashMap<usize, Options>,
+    middens: Vec<Coord>,
+    diplomacy: Diplomacy,
+    /// Surface end of every shaft between the layers.
+    entrances: Vec<Coord>,
+    food_drops: usize,
+    /// Cells holding water, the only ones the flow has to visit.
+    wet: HashSet<Coord>,
+    /// Round each cell was last contaminated by a sick ant.
+    contaminated: HashMap<Coord, usize>,
+    /// Disease history by team id, oldest sample first.
+    sir: HashMap<usize, Vec<Sir>>,
+    deliveries: HashMap<usize, Deliveries>,
+    /// Enemies killed in combat by team id.
+    kills: HashMap<usize, usize>,
 }
 
 impl AntGrid {
@@ -31,20 +68,36 @@ impl AntGrid {
             return true;
         }
         let get = self.grid.get(coord);
+        if self
+            .grid
+            .tile(coord)
+            .terrain()
+            .map_or(false, |t| t.dig_cost().is_none())
+        {
+            return true;
+        }
         return get.elem.clone().map_or(false, |g| {
             return g.borrow().exists();
         });
     }
+    pub(super) fn options_for(&self, team: &Team) -> Options {
+        return self
+            .team_options
+            .get(&team.id)
+            .copied()
+            .unwrap_or(self.options);
+    }
     pub(super) fn send_signal(&mut self, pt: &Coord, signal: Signal, team_elem: TeamElement) {
-        for mut i in self
-            .elements
-            .get_vec_mut(&team_elem)
-            .unwrap()
-            .iter()
-            .filter_map(|f| f.try_borrow_mut().ok())
-            .filter(|f| f.pos().distance(pt) < self.options.signal_radius)
-        {
-            i.recv_signal(signal);
+        let radius = team_elem
+            .team
+            .as_ref()
+            .map_or(self.options, |t| self.options_for(t))
+            .signal_radius;
+        self.signal_group(pt, signal, &team_elem, radius);
+        if signal.signal_type == SignalType::Battle {
+            for ally in self.allies(&team_elem) {
+                self.signal_group(pt, signal, &ally, radius);
+            }
         }
         match self.elements.get_vec_mut(&TeamElement {
             element: ElementType::Hive,
@@ -52,45 +105,243 @@ impl AntGrid {
         }) {
             None => (),
             Some(l) => {
-                for mut i in l
+                let nearest = l
                     .iter()
                     .filter_map(|f| f.try_borrow_mut().ok())
-                    .filter(|f| f.pos().distance(pt) < self.options.signal_radius)
-                {
-                    i.recv_signal(signal);
+                    .filter(|f| f.pos().distance(pt) < radius)
+                    .min_by(|a, b| {
+                        a.pos()
+                            .distance(pt)
+                            .partial_cmp(&b.pos().distance(pt))
+                            .unwrap()
+                    });
+                if let Some(mut hive) = nearest {
+                    hive.recv_signal(signal);
                 }
             }
         }
     }
-    pub(super) fn attack(&mut self, coord: &Coord, team: &Team) {
+    fn signal_group(&mut self, pt: &Coord, signal: Signal, key: &TeamElement, radius: f64) {
+        for mut i in self
+            .elements
+            .get_vec_mut(key)
+            .into_iter()
+            .flatten()
+            .filter_map(|f| f.try_borrow_mut().ok())
+            .filter(|f| f.pos().distance(pt) < radius)
+        {
+            i.recv_signal(signal);
+        }
+    }
+    /// The same kind of element in every team allied to the one of `team_elem`.
+    fn allies(&self, team_elem: &TeamElement) -> Vec<TeamElement> {
+        let team = match &team_elem.team {
+            None => return Vec::new(),
+            Some(team) => team,
+        };
+        return self
+            .elements
+            .keys()
+            .filter(|k| k.element == team_elem.element)
+            .filter(|k| {
+                k.team.as_ref().map_or(false, |t| {
+                    t != team && self.relation(t, team) == Relation::Allied
+                })
+            })
+            .cloned()
+            .collect();
+    }
+    /// Strikes the enemy at `coord` from `from`, harder for every other ant of `team` flanking
+    /// it, and credits `team` when the blow kills an ant.
+    pub(super) fn attack(&mut self, from: &Coord, coord: &Coord, team: &Team, damage: usize) {
         assert!(self.is_enemy(coord, team));
-        self.damage(coord);
+        let flankers = self
+            .neighbours(coord)
+            .iter()
+            .filter(|c| *c != from && self.ant_team(c).as_ref() == Some(team))
+            .count();
+        let bonus = 100 + self.options_for(team).flanking * flankers;
+        let (armour, was_alive, is_ant) = {
+            let target = self.grid.get(coord).get_elem();
+            let target = target.borrow();
+            (
+                target.armour(),
+                !target.is_removed(),
+                target.type_elem() == ElementType::Ant,
+            )
+        };
+        let damage = (0..damage * bonus / 100)
+            .filter(|_| self.rng.gen_range(0..100) >= armour)
+            .count();
+        self.damage(coord, damage);
+        if was_alive && is_ant && self.grid.get(coord).get_elem().borrow().is_removed() {
+            *self.kills.entry(team.id).or_default() += 1;
+        }
+    }
+    pub fn kills(&self, team: &Team) -> usize {
+        return self.kills.get(&team.id).copied().unwrap_or(0);
     }
 
-    pub(super) fn pass_food(&mut self, coord: &Coord, pheromones: usize) -> Option<usize> {
+    pub(super) fn pass_food(
+        &mut self,
+        coord: &Coord,
+        pheromones: usize,
+        food: usize,
+    ) -> Option<usize> {
         assert!(self.is_blocked(coord));
         return self
             .grid
             .get_mut(coord)
             .get_elem()
             .borrow_mut()
-            .pass_food(pheromones);
+            .pass_food(pheromones, food);
     }
-    fn damage(&mut self, coord: &Coord) {
+    pub(super) fn record_delivery(&mut self, team: &Team, relayed: bool) {
+        let deliveries = self.deliveries.entry(team.id).or_default();
+        match relayed {
+            true => deliveries.relayed += 1,
+            false => deliveries.direct += 1,
+        }
+    }
+    pub(super) fn record_handoff(&mut self, team: &Team) {
+        self.deliveries.entry(team.id).or_default().handoffs += 1;
+    }
+    pub fn deliveries(&self, team: &Team) -> Deliveries {
+        return self.deliveries.get(&team.id).copied().unwrap_or_default();
+    }
+    fn damage(&mut self, coord: &Coord, damage: usize) {
         let ant = self.grid.get(coord).elem.clone().unwrap();
         let mut other_entity = ant.borrow_mut();
-        other_entity.attacked(1);
+        other_entity.attacked(damage);
     }
-    pub(super) fn eat_food(&mut self, coord: &Coord) {
+    /// Takes up to `amount` units from the food at `coord`, returning the nutrition taken.
+    pub(super) fn eat_food(&mut self, coord: &Coord, amount: usize) -> usize {
         assert!(self.is_food(coord));
-        self.damage(coord);
+        let (food, quality) = {
+            let elem = self.grid.get(coord).get_elem();
+            let elem = elem.borrow();
+            (elem.food(), elem.quality())
+        };
+        let eaten = amount.min(food);
+        self.damage(coord, eaten);
+        return eaten * quality;
     }
-    pub(super) fn remove_dirt(&mut self, coord: &Coord) {
+    pub(super) fn food_kind(&self, coord: &Coord) -> Option<FoodKind> {
+        return self.grid.get(coord).get_elem().borrow().food_kind();
+    }
+    pub(super) fn take_food(&mut self, coord: &Coord, amount: usize) -> usize {
+        return self
+            .grid
+            .get(coord)
+            .get_elem()
+            .borrow_mut()
+            .take_food(amount);
+    }
+    /// Digs at `coord` once, returning whether the cell is now open.
+    pub(super) fn remove_dirt(&mut self, coord: &Coord) -> bool {
         assert!(self.is_dirt(coord));
-        self.damage(coord);
+        return self.grid.tile_mut(coord).dig();
+    }
+    /// Whether dug dirt can be dropped on `coord`.
+    pub(super) fn can_deposit(&self, coord: &Coord) -> bool {
+        return !self.is_blocked(coord)
+            && !self.is_dirt(coord)
+            && !self.grid.tile(coord).entrance
+            && self.grid.get(coord).elem.is_none();
+    }
+    /// Nearest hostile ant or predator within `radius` of `from` on its layer.
+    pub(super) fn nearest_enemy(&self, from: &Coord, team: &Team, radius: f64) -> Option<Coord> {
+        return self
+            .elements
+            .iter_all()
+            .filter(|(key, _)| match &key.team {
+                Some(t) => {
+                    key.element == ElementType::Ant && self.relation(t, team) == Relation::Hostile
+                }
+                None => key.element == ElementType::Predator,
+            })
+            .flat_map(|(_, enemies)| enemies.iter())
+            .filter_map(|e| e.try_borrow().ok())
+            .filter(|e| !e.is_removed() && e.pos().layer == from.layer)
+            .map(|e| e.pos().clone())
+            .filter(|pos| pos.distance(from) < radius)
+            .min_by(|a, b| a.distance(from).partial_cmp(&b.distance(from)).unwrap());
+    }
+    /// Nearest midden to `from`, or the nearest of the team's hive entrances without one.
+    pub(super) fn dump_site(&self, from: &Coord, team: &Team) -> Option<Coord> {
+        let sites = if self.middens.is_empty() {
+            self.hives(team)
+                .iter()
+                .map(|h| match self.entrance_spot(h) {
+                    Some(spot) if self.layers() > 1 => Coord { layer: 0, ..spot },
+                    _ => *h,
+                })
+                .collect()
+        } else {
+            self.middens.clone()
+        };
+        return sites.into_iter().min_by(|a, b| {
+            self.route_distance(from, a)
+                .partial_cmp(&self.route_distance(from, b))
+                .unwrap()
+        });
+    }
+    /// Cells reachable from `c` in one step, including the other ends of a shaft.
+    pub(super) fn neighbours(&self, c: &Coord) -> Vec<Coord> {
+        let mut cells: Vec<Coord> = Dir::iter().filter_map(|d| c.next_cell(&d)).collect();
+        if self.grid.does_exist(c) && self.grid.tile(c).entrance {
+            cells.extend(
+                [c.layer.checked_sub(1), Some(c.layer + 1)]
+                    .into_iter()
+                    .flatten()
+                    .map(|layer| Coord { layer, ..*c })
+                    .filter(|n| self.grid.does_exist(n) && self.grid.tile(n).entrance),
+            );
+        }
+        return cells;
+    }
+    /// Walking distance from `from` to `to`, through the best shaft when they are on
+    /// different layers.
+    pub(super) fn route_distance(&self, from: &Coord, to: &Coord) -> f64 {
+        if from.layer == to.layer {
+            return from.distance(to);
+        }
+        let climb = from.layer.abs_diff(to.layer) as f64;
+        return self
+            .entrances
+            .iter()
+            .map(|e| from.distance(e) + e.distance(to) + climb)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap_or(f64::INFINITY);
+    }
+    /// Where the shaft of the hive at `hive` comes out, just above it where there is room.
+    fn entrance_spot(&self, hive: &Coord) -> Option<Coord> {
+        let y = Some(hive.y + 1)
+            .filter(|y| *y < self.rows())
+            .or(hive.y.checked_sub(1))?;
+        return Some(Coord { y, ..*hive });
+    }
+    /// Opens a shaft through every layer next to the hive at `hive`.
+    fn add_entrance(&mut self, hive: &Coord) {
+        let spot = match self.entrance_spot(hive) {
+            Some(spot) if self.layers() > 1 => spot,
+            _ => return,
+        };
+        for layer in 0..self.layers() {
+            let tile = self.grid.tile_mut(&Coord { layer, ..spot });
+            tile.clear();
+            tile.entrance = true;
+        }
+        self.entrances.push(Coord { layer: 0, ..spot });
+    }
+    pub(super) fn terrain(&self, coord: &Coord) -> Option<Terrain> {
+        if !self.grid.does_exist(coord) {
+            return None;
+        }
+        return self.grid.tile(coord).terrain();
     }
     pub(super) fn is_dirt(&self, coord: &Coord) -> bool {
-        return self.is_same_team_elem(coord, &DIRT_ELEMENT);
+        return self.grid.does_exist(coord) && self.grid.tile(coord).is_solid();
     }
     pub(super) fn is_enemy(&self, coord: &Coord, team: &Team) -> bool {
         if !self.grid.does_exist(coord) {
@@ -100,22 +351,242 @@ impl AntGrid {
 
         let other_team = match ant {
             None => None,
+            Some(a) if a.borrow().is_removed() => return false,
+            Some(a) if a.borrow().type_elem() == ElementType::Predator => return true,
+            Some(a) if a.borrow().type_elem() == ElementType::Corpse => return false,
             Some(a) => a.borrow().team(),
         };
         return match other_team {
             None => false,
-            Some(t) => &t != team,
+            Some(t) => self.relation(&t, team) == Relation::Hostile,
+        };
+    }
+    pub(super) fn is_ant(&self, coord: &Coord) -> bool {
+        if !self.grid.does_exist(coord) {
+            return false;
+        }
+        return self.grid.get(coord).elem.as_ref().map_or(false, |e| {
+            e.borrow().type_elem() == ElementType::Ant && !e.borrow().is_removed()
+        });
+    }
+    pub(super) fn ant_team(&self, coord: &Coord) -> Option<Team> {
+        if !self.is_ant(coord) {
+            return None;
+        }
+        return self.grid.get(coord).get_elem().borrow().team();
+    }
+    pub(super) fn is_predator(&self, coord: &Coord) -> bool {
+        return self.is_same_team_elem(
+            coord,
+            &TeamElement {
+                element: ElementType::Predator,
+                team: None,
+            },
+        );
+    }
+    pub(super) fn is_aphid(&self, coord: &Coord) -> bool {
+        return self.is_same_team_elem(
+            coord,
+            &TeamElement {
+                element: ElementType::Aphid,
+                team: None,
+            },
+        );
+    }
+    /// Whether the cell holds an aphid with honeydew to collect.
+    pub(super) fn has_honeydew(&self, coord: &Coord) -> bool {
+        return self.is_same_team_elem(
+            coord,
+            &TeamElement {
+                element: ElementType::Aphid,
+                team: None,
+            },
+        ) && self.grid.get(coord).get_elem().borrow().food() > 0;
+    }
+    pub(super) fn is_garden(&self, coord: &Coord, team: &Team) -> bool {
+        return self.is_same_team_elem(
+            coord,
+            &TeamElement {
+                element: ElementType::Garden,
+                team: Some(team.clone()),
+            },
+        );
+    }
+    /// Whether the cell holds another team's garden with fungus to raid.
+    pub(super) fn is_enemy_garden(&self, coord: &Coord, team: &Team) -> bool {
+        if !self.grid.does_exist(coord) {
+            return false;
+        }
+        let elem = self.grid.get(coord).get_elem();
+        let elem = elem.borrow();
+        return elem.type_elem() == ElementType::Garden
+            && !elem.is_removed()
+            && elem
+                .team()
+                .map_or(false, |t| self.relation(&t, team) == Relation::Hostile)
+            && elem.food() > 0;
+    }
+    /// Adds leaf material to the garden at `coord`.
+    pub(super) fn feed_garden(&mut self, coord: &Coord, material: usize) {
+        assert!(self.grid.get(coord).elem.is_some());
+        self.grid
+            .get(coord)
+            .get_elem()
+            .borrow_mut()
+            .recv_signal(Signal {
+                coord: *coord,
+                signal_type: SignalType::Deliver(material),
+                propagate: 0,
+            });
+    }
+    /// Whether the cell holds a dead nestmate not yet carried to a waste pile.
+    pub(super) fn is_nestmate_corpse(&self, coord: &Coord, team: &Team) -> bool {
+        return self.is_same_team_elem(
+            coord,
+            &TeamElement {
+                element: ElementType::Corpse,
+                team: Some(team.clone()),
+            },
+        ) && !self.grid.get(coord).get_elem().borrow().is_disposed();
+    }
+    pub(super) fn is_corpse(&self, coord: &Coord) -> bool {
+        if !self.grid.does_exist(coord) {
+            return false;
+        }
+        let elem = self.grid.get(coord).get_elem();
+        let elem = elem.borrow();
+        return elem.type_elem() == ElementType::Corpse && !elem.is_removed();
+    }
+    pub(super) fn is_enemy_corpse(&self, coord: &Coord, team: &Team) -> bool {
+        if !self.grid.does_exist(coord) {
+            return false;
+        }
+        let elem = self.grid.get(coord).get_elem();
+        let elem = elem.borrow();
+        return elem.type_elem() == ElementType::Corpse
+            && !elem.is_removed()
+            && elem.team().as_ref() != Some(team);
+    }
+    /// Nearest corpse within `radius` of `from` on its layer.
+    pub(super) fn nearest_corpse(&self, from: &Coord, radius: f64) -> Option<Coord> {
+        return self
+            .elements
+            .iter_all()
+            .filter(|(key, _)| key.element == ElementType::Corpse)
+            .flat_map(|(_, corpses)| corpses.iter())
+            .filter_map(|c| c.try_borrow().ok())
+            .filter(|c| !c.is_removed() && c.pos().layer == from.layer)
+            .map(|c| c.pos().clone())
+            .filter(|pos| pos.distance(from) < radius)
+            .min_by(|a, b| a.distance(from).partial_cmp(&b.distance(from)).unwrap());
+    }
+    /// Nearest midden to `from`, or a spot on the surface away from the team's nearest hive.
+    pub(super) fn waste_site(&self, from: &Coord, team: &Team) -> Coord {
+        const DISTANCE: usize = 10;
+        let nearest = |sites: Vec<Coord>| {
+            sites.into_iter().min_by(|a, b| {
+                self.route_distance(from, a)
+                    .partial_cmp(&self.route_distance(from, b))
+                    .unwrap()
+            })
+        };
+        if let Some(midden) = nearest(self.middens.clone()) {
+            return midden;
+        }
+        return match nearest(self.hives(team)) {
+            None => *from,
+            Some(hive) => Coord {
+                x: if hive.x + DISTANCE < self.cols() {
+                    hive.x + DISTANCE
+                } else {
+                    hive.x.saturating_sub(DISTANCE)
+                },
+                y: hive.y,
+                layer: 0,
+            },
         };
     }
+    pub(super) fn is_infectious(&self, coord: &Coord) -> bool {
+        if !self.grid.does_exist(coord) {
+            return false;
+        }
+        return self.grid.get(coord).elem.as_ref().map_or(false, |e| {
+            let e = e.borrow();
+            e.is_infectious() && !e.is_removed()
+        });
+    }
+    pub(super) fn is_sick_nestmate(&self, coord: &Coord, team: &Team) -> bool {
+        return self.ant_team(coord).as_ref() == Some(team)
+            && self
+                .grid
+                .get(coord)
+                .get_elem()
+                .borrow()
+                .infection()
+                .is_infected();
+    }
+    pub(super) fn groom(&mut self, coord: &Coord, rounds: usize) {
+        self.grid.get(coord).get_elem().borrow_mut().groom(rounds);
+    }
+    /// Marks the cell as carrying the disease for a while.
+    pub(super) fn contaminate(&mut self, coord: &Coord) {
+        self.contaminated.insert(*coord, self.round_num);
+    }
+    pub(super) fn is_contaminated(&self, coord: &Coord) -> bool {
+        return self
+            .contaminated
+            .get(coord)
+            .map_or(false, |shed| self.round_num - shed < CONTAMINATION_ROUNDS);
+    }
+    /// Whether the cell holds an enemy hive with food in store.
+    pub(super) fn is_enemy_stores(&self, coord: &Coord, team: &Team) -> bool {
+        return self.is_enemy(coord, team)
+            && self.grid.get(coord).get_elem().borrow().type_elem() == ElementType::Hive
+            && self.grid.get(coord).get_elem().borrow().food() > 0;
+    }
+    /// Steals up to `amount` food from the hive at `coord`, which calls its ants to defend it.
+    pub(super) fn raid_hive(&mut self, coord: &Coord, amount: usize) -> usize {
+        let (taken, team) = {
+            let hive = self.grid.get(coord).get_elem();
+            let mut hive = hive.borrow_mut();
+            (hive.take_food(amount), hive.team().unwrap())
+        };
+        let defenders = TeamElement {
+            element: ElementType::Ant,
+            team: Some(team.clone()),
+        };
+        if self.elements.get_vec(&defenders).is_some() {
+            let propagate = self.options_for(&team).propagation;
+            self.send_signal(
+                coord,
+                Signal {
+                    coord: *coord,
+                    signal_type: SignalType::Battle,
+                    propagate,
+                },
+                defenders,
+            );
+        }
+        return taken;
+    }
+    pub(super) fn is_entrance(&self, coord: &Coord) -> bool {
+        return self.grid.does_exist(coord) && self.grid.tile(coord).entrance;
+    }
+    /// A predator biting the ant at `coord`.
+    pub(super) fn hunt(&mut self, coord: &Coord, damage: usize) {
+        assert!(self.is_ant(coord) || self.is_aphid(coord));
+        self.damage(coord, damage);
+    }
     pub(super) fn is_food(&self, coord: &Coord) -> bool {
-        return self.is_same_team_elem(coord, &FOOD_ELEMENT);
+        return self.is_same_team_elem(coord, &FOOD_ELEMENT)
+            && self.grid.get(coord).get_elem().borrow().food() > 0;
     }
-    pub(super) fn is_hive_same_team(&self, coord: &Coord, team: Team) -> bool {
+    pub(super) fn is_hive_same_team(&self, coord: &Coord, team: &Team) -> bool {
         return self.is_same_team_elem(
             coord,
             &TeamElement {
                 element: ElementType::Hive,
-                team: Some(team),
+                team: Some(team.clone()),
             },
         );
     }
@@ -127,20 +598,84 @@ impl AntGrid {
         let elem = ant.borrow().team_element();
         return elem == *team_elem && !ant.borrow().is_removed();
     }
-    pub(super) fn hive_exists(&mut self, team: Team) -> bool {
+    pub(super) fn hive_exists(&mut self, team: &Team) -> bool {
         return self
             .elements
-            .get(&TeamElement {
+            .get_vec(&TeamElement {
                 element: Hive,
-                team: Some(team),
+                team: Some(team.clone()),
             })
-            .is_some();
+            .map_or(false, |hives| {
+                hives
+                    .iter()
+                    .any(|h| h.try_borrow().map_or(true, |h| !h.is_removed()))
+            });
     }
-    pub(super) fn get_pheromones(&mut self, pt: &Coord, team: Team, state_bool: bool) -> usize {
-        self.grid
-            .get_mut(pt)
-            .clear_old(self.round_num, self.options.decay);
-        let key = (team, state_bool);
+    /// Positions of the team's standing hives, skipping one that is currently deciding.
+    pub(super) fn hives(&self, team: &Team) -> Vec<Coord> {
+        return self
+            .elements
+            .get_vec(&TeamElement {
+                element: Hive,
+                team: Some(team.clone()),
+            })
+            .map_or(Vec::new(), |hives| {
+                hives
+                    .iter()
+                    .filter_map(|h| h.try_borrow().ok())
+                    .filter(|h| !h.is_removed())
+                    .map(|h| h.pos().clone())
+                    .collect()
+            });
+    }
+    /// Picks a random cell at least `distance` away from all of the team's hives.
+    pub(super) fn expansion_site(
+        &mut self,
+        team: &Team,
+        from: &Coord,
+        distance: usize,
+    ) -> Option<Coord> {
+        let hives = self.hives(team);
+        for _ in 0..10 {
+            let site = Coord {
+                x: self.rng.gen_range(0..self.cols()),
+                y: self.rng.gen_range(0..self.rows()),
+                layer: from.layer,
+            };
+            if !self.is_blocked(&site)
+                && hives
+                    .iter()
+                    .chain([from])
+                    .all(|h| h.distance(&site) >= distance as f64)
+            {
+                return Some(site);
+            }
+        }
+        return None;
+    }
+    fn clear_old(&mut self, pt: &Coord) {
+        let (options, team_options) = (self.options, &self.team_options);
+        let factor = self.clock().decay_factor();
+        self.grid.get_mut(pt).clear_old(self.round_num, |team| {
+            team_options.get(&team.id).unwrap_or(&options).decay * factor / 100
+        });
+    }
+    pub(super) fn population(&self, team: &Team) -> usize {
+        return self
+            .elements
+            .get_vec(&TeamElement {
+                element: ElementType::Ant,
+                team: Some(team.clone()),
+            })
+            .map_or(0, |ants| {
+                ants.iter()
+                    .filter(|a| a.try_borrow().map_or(true, |a| !a.is_removed()))
+                    .count()
+            });
+    }
+    pub(super) fn get_pheromones(&mut self, pt: &Coord, team: &Team, state_bool: bool) -> usize {
+        self.clear_old(pt);
+        let key = (team.clone(), state_bool);
         return self
             .grid
             .get(pt)
@@ -156,9 +691,7 @@ impl AntGrid {
         team: &Team,
         state_bool: bool,
     ) {
-        self.grid
-            .get_mut(&pos)
-            .clear_old(self.round_num, self.options.decay);
+        self.clear_old(&pos);
         let key = (team.clone(), state_bool);
         let old = self.grid.get_mut(&pos).pheromones.get(&key);
         let old_val = old.clone().map(|a| a.pheromones).unwrap_or(usize::MAX);
@@ -175,24 +708,31 @@ impl AntGrid {
 }
 
 impl AntGrid {
-    pub fn new(rows: usize, cols: usize) -> Self {
+    pub fn new(rows: usize, cols: usize, layers: usize) -> Self {
         AntGrid {
-            grid: Grid::new(rows, cols),
+            grid: Grid::new(rows, cols, layers.max(1)),
             elements: MultiMap::new(),
             options: Options::default(),
+            team_options: HashMap::new(),
+            middens: Vec::new(),
+            diplomacy: Diplomacy::default(),
+            entrances: Vec::new(),
+            food_drops: 0,
+            wet: HashSet::new(),
+            contaminated: HashMap::new(),
+            sir: HashMap::new(),
+            deliveries: HashMap::new(),
+            kills: HashMap::new(),
             round_num: 0,
             rng: SmallRng::from_entropy(),
         }
     }
     pub fn run_round(&mut self) {
+        self.diplomacy.advance(self.round_num);
         let mut to_iter: VecDeque<(usize, Rc<RefCell<dyn GridElement>>)> = self
             .elements
             .keys()
-            .filter(|e| {
-                e.element != ElementType::Dirt
-                    && e.element != ElementType::Empty
-                    && e.element != ElementType::Food
-            })
+            .filter(|e| e.element != ElementType::Empty)
             .map(|t| t.clone())
             .map(|k| {
                 self.elements
@@ -208,7 +748,10 @@ impl AntGrid {
         while !to_iter.is_empty() {
             let (_idx, ant) = to_iter.pop_front().unwrap();
             let old_pos = ant.borrow().pos().clone();
-            self.grid.get_mut(&old_pos).elem = None;
+            let cell = &mut self.grid.get_mut(&old_pos).elem;
+            if cell.as_ref().map_or(false, |e| Rc::ptr_eq(e, &ant)) {
+                *cell = None;
+            }
             if !ant.borrow().is_removed() {
                 let c = ant.borrow_mut().decide(self);
                 if !ant.borrow().is_removed() {
@@ -216,6 +759,15 @@ impl AntGrid {
                 }
             }
         }
+        let dead: Vec<(Coord, Team, bool)> = self
+            .elements
+            .iter_all()
+            .filter(|(key, _)| key.element == ElementType::Ant)
+            .flat_map(|(_, ants)| ants.iter())
+            .map(|ant| ant.borrow())
+            .filter(|ant| ant.is_removed())
+            .filter_map(|ant| Some((ant.pos().clone(), ant.team()?, ant.is_infectious())))
+            .collect();
         let keys: Vec<TeamElement> = self.elements.keys().map(|f| f.to_owned()).collect();
         for key in keys {
             self.elements
@@ -223,8 +775,379 @@ impl AntGrid {
                 .unwrap()
                 .drain_filter(|f| f.borrow().is_removed());
         }
+        for (pos, team, infected) in dead {
+            self.leave_corpse(&pos, &team, infected);
+        }
+        let options = self.options;
+        let food_factor = self.clock().food_factor();
+        if options.food_drop_rounds > 0 && self.round_num % options.food_drop_rounds == 0 {
+            self.drop_food(
+                options.food_drop_amount * food_factor / 100,
+                options.food_pattern,
+            );
+        }
+        if options.food_burst_rounds > 0
+            && self.round_num > 0
+            && self.round_num % options.food_burst_rounds == 0
+        {
+            self.drop_food(
+                options.food_burst_amount * food_factor / 100,
+                options.food_pattern,
+            );
+        }
+        if options.scent_radius > 0 && self.round_num % SCENT_ROUNDS == 0 {
+            self.spread_scent(options.scent_radius.min(u8::MAX as usize) as u8);
+        }
+        if options.rain_rounds > 0 && self.round_num % options.rain_rounds == 0 {
+            self.rain(options.rain_amount);
+        }
+        self.flow_water();
+        if options.evaporation > 0 && self.round_num % options.evaporation == 0 {
+            self.evaporate();
+        }
+        if options.predator_rounds > 0
+            && self.round_num > 0
+            && self.round_num % options.predator_rounds == 0
+        {
+            self.spawn_predators(1);
+        }
+        if options.outbreak_rounds > 0
+            && self.round_num > 0
+            && self.round_num % options.outbreak_rounds == 0
+        {
+            self.outbreak();
+        }
+        if self.round_num % SIR_ROUNDS == 0 {
+            self.record_sir();
+        }
+        let collapse_rounds = self.options.collapse_rounds;
+        if collapse_rounds > 0 && self.round_num % collapse_rounds == 0 {
+            self.settle(self.options.support);
+        }
         self.round_num += 1;
     }
+    /// Scatters up to `amount` food items over open surface cells following `pattern`.
+    fn drop_food(&mut self, amount: usize, pattern: FoodPattern) {
+        const PATCH_RADIUS: isize = 4;
+        let (rows, cols) = (self.rows(), self.cols());
+        let centre = (self.rng.gen_range(0..cols), self.rng.gen_range(0..rows));
+        let front = self.food_drops % cols;
+        self.food_drops += 1;
+        for _ in 0..amount {
+            let (x, y) = match pattern {
+                FoodPattern::Uniform => (self.rng.gen_range(0..cols), self.rng.gen_range(0..rows)),
+                FoodPattern::Patches => {
+                    let dx = self.rng.gen_range(-PATCH_RADIUS..=PATCH_RADIUS);
+                    let dy = self.rng.gen_range(-PATCH_RADIUS..=PATCH_RADIUS);
+                    match (
+                        centre.0.checked_add_signed(dx),
+                        centre.1.checked_add_signed(dy),
+                    ) {
+                        (Some(x), Some(y)) => (x, y),
+                        _ => continue,
+                    }
+                }
+                FoodPattern::Front => (front, self.rng.gen_range(0..rows)),
+            };
+            let pos = Coord { x, y, layer: 0 };
+            if self.grid.does_exist(&pos) && !self.is_blocked(&pos) {
+                self.put_food(&pos);
+            }
+        }
+    }
+    /// Rebuilds the scent field outwards from every food item, dirt blocks the scent.
+    fn spread_scent(&mut self, radius: u8) {
+        self.grid.tiles_mut().for_each(|t| t.scent = 0);
+        let mut queue: VecDeque<Coord> = self
+            .elements
+            .get_vec(&FOOD_ELEMENT)
+            .map_or(Vec::new(), |food| {
+                food.iter()
+                    .filter(|f| f.borrow().food() > 0)
+                    .map(|f| f.borrow().pos().clone())
+                    .collect()
+            })
+            .into();
+        for pos in queue.iter() {
+            self.grid.tile_mut(pos).scent = radius;
+        }
+        while let Some(pos) = queue.pop_front() {
+            let scent = self.grid.tile(&pos).scent - 1;
+            if scent == 0 {
+                continue;
+            }
+            for next in self.neighbours(&pos) {
+                if self.grid.does_exist(&next)
+                    && !self.is_dirt(&next)
+                    && self.grid.tile(&next).scent < scent
+                {
+                    self.grid.tile_mut(&next).scent = scent;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    /// Lays a corpse where an ant died, unless something else already took the cell.
+    fn leave_corpse(&mut self, pos: &Coord, team: &Team, infected: bool) {
+        let taken = self.grid.get(pos).elem.as_ref().map_or(false, |e| {
+            let e = e.borrow();
+            e.exists() && !e.is_removed()
+        });
+        if taken || self.is_dirt(pos) {
+            return;
+        }
+        self.grid.get_mut(pos).elem = None;
+        self.put(Corpse::new(pos, team, false, infected));
+    }
+    /// Infects one random living ant.
+    fn outbreak(&mut self) {
+        let ants: Vec<Rc<RefCell<dyn GridElement>>> = self
+            .elements
+            .iter_all()
+            .filter(|(key, _)| key.element == ElementType::Ant)
+            .flat_map(|(_, ants)| ants.iter())
+            .filter(|ant| !ant.borrow().is_removed())
+            .cloned()
+            .collect();
+        if ants.is_empty() {
+            return;
+        }
+        let ant = &ants[self.rng.gen_range(0..ants.len())];
+        let rounds = self
+            .options_for(&ant.borrow().team().unwrap())
+            .sickness_rounds;
+        ant.borrow_mut().infect(rounds);
+    }
+    /// Samples how many ants of each team are susceptible, infected and recovered.
+    fn record_sir(&mut self) {
+        let mut samples: HashMap<usize, Sir> = HashMap::new();
+        for (key, ants) in self.elements.iter_all() {
+            let team = match (&key.team, key.element) {
+                (Some(team), ElementType::Ant) => team,
+                _ => continue,
+            };
+            let sample = samples.entry(team.id).or_insert(Sir {
+                round: self.round_num,
+                ..Sir::default()
+            });
+            for ant in ants.iter().map(|a| a.borrow()).filter(|a| !a.is_removed()) {
+                sample.count(ant.infection());
+            }
+        }
+        for (team, sample) in samples {
+            let history = self.sir.entry(team).or_default();
+            if history.len() == SIR_HISTORY {
+                history.remove(0);
+            }
+            history.push(sample);
+        }
+        let round = self.round_num;
+        self.contaminated
+            .retain(|_, shed| round - *shed < CONTAMINATION_ROUNDS);
+    }
+    pub fn relation(&self, a: &Team, b: &Team) -> Relation {
+        return self.diplomacy.relation(a.id, b.id);
+    }
+    pub fn set_relation(&mut self, a: usize, b: usize, relation: Relation) {
+        self.diplomacy.set(a, b, relation);
+    }
+    pub fn schedule_treaty(&mut self, treaty: Treaty) {
+        self.diplomacy.schedule(treaty);
+    }
+    /// Teams still holding hives once none of them are hostile to each other.
+    pub fn victors(&self) -> Option<Vec<Team>> {
+        let standing: Vec<Team> = self
+            .elements
+            .iter_all()
+            .filter(|(key, _)| key.element == Hive)
+            .filter(|(_, hives)| {
+                hives
+                    .iter()
+                    .any(|h| h.try_borrow().map_or(true, |h| !h.is_removed()))
+            })
+            .filter_map(|(key, _)| key.team.clone())
+            .collect();
+        let hostile = standing.iter().any(|a| {
+            standing
+                .iter()
+                .any(|b| self.relation(a, b) == Relation::Hostile)
+        });
+        if standing.is_empty() || hostile {
+            return None;
+        }
+        return Some(standing);
+    }
+    /// Disease history by team id, sampled every few rounds.
+    pub fn sir(&self) -> &HashMap<usize, Vec<Sir>> {
+        return &self.sir;
+    }
+    /// Releases `amount` predators of random kinds on open surface cells.
+    pub fn spawn_predators(&mut self, amount: usize) {
+        let kinds: Vec<PredatorKind> = PredatorKind::iter().collect();
+        for _ in 0..amount {
+            let pos = Coord {
+                x: self.rng.gen_range(0..self.cols()),
+                y: self.rng.gen_range(0..self.rows()),
+                layer: 0,
+            };
+            let kind = kinds[self.rng.gen_range(0..kinds.len())];
+            if !self.is_blocked(&pos) && !self.is_dirt(&pos) {
+                self.put(Predator::new(&pos, kind));
+            }
+        }
+    }
+    /// Places `amount` aphids on random open surface cells.
+    pub fn spawn_aphids(&mut self, amount: usize) {
+        for _ in 0..amount {
+            let pos = Coord {
+                x: self.rng.gen_range(0..self.cols()),
+                y: self.rng.gen_range(0..self.rows()),
+                layer: 0,
+            };
+            if !self.is_blocked(&pos) && !self.is_dirt(&pos) {
+                self.put(Aphid::new(&pos));
+            }
+        }
+    }
+    /// Wets `amount` random open cells of the surface, the top row on a single layer world.
+    fn rain(&mut self, amount: usize) {
+        for _ in 0..amount {
+            let x = self.rng.gen_range(0..self.cols());
+            let y = match self.layers() {
+                1 => self.rows() - 1,
+                _ => self.rng.gen_range(0..self.rows()),
+            };
+            self.put_water(&Coord { x, y, layer: 0 }, 1);
+        }
+    }
+    /// Cells water at `c` falls into, down a shaft or down the tunnel on a side-on nest layer,
+    /// and the cells it levels out into after.
+    fn water_paths(&self, c: &Coord) -> (Vec<Coord>, Vec<Coord>) {
+        let mut down = Vec::new();
+        let below = Coord {
+            layer: c.layer + 1,
+            ..*c
+        };
+        if self.grid.tile(c).entrance
+            && self.grid.does_exist(&below)
+            && self.grid.tile(&below).entrance
+        {
+            down.push(below);
+        }
+        if c.layer > 0 || self.layers() == 1 {
+            down.extend(c.next_cell(&Dir::DOWN));
+            let sideways = [Dir::LEFT, Dir::RIGHT]
+                .iter()
+                .filter_map(|d| c.next_cell(d))
+                .collect();
+            return (down, sideways);
+        }
+        return (down, Dir::iter().filter_map(|d| c.next_cell(&d)).collect());
+    }
+    /// Moves water one step through open cells, falling first and then levelling out.
+    /// Standing water washes away pheromones.
+    fn flow_water(&mut self) {
+        let wet: Vec<Coord> = self.wet.drain().collect();
+        for c in wet {
+            let mut depth = self.grid.tile(&c).water;
+            if depth == 0 {
+                continue;
+            }
+            self.grid.get_mut(&c).pheromones.clear();
+            let (down, sideways) = self.water_paths(&c);
+            for next in down {
+                if !self.grid.does_exist(&next) || self.is_dirt(&next) {
+                    continue;
+                }
+                let tile = self.grid.tile_mut(&next);
+                let moved = depth.min(MAX_WATER.saturating_sub(tile.water));
+                tile.water += moved;
+                depth -= moved;
+                if moved > 0 {
+                    self.wet.insert(next);
+                }
+            }
+            for next in sideways {
+                if !self.grid.does_exist(&next) || self.is_dirt(&next) {
+                    continue;
+                }
+                let tile = self.grid.tile_mut(&next);
+                if tile.water + 1 < depth {
+                    tile.water += 1;
+                    depth -= 1;
+                    self.wet.insert(next);
+                }
+            }
+            self.grid.tile_mut(&c).water = depth;
+            if depth > 0 {
+                self.wet.insert(c);
+            }
+        }
+    }
+    fn evaporate(&mut self) {
+        for c in self.wet.iter() {
+            let tile = self.grid.tile_mut(c);
+            tile.water = tile.water.saturating_sub(1);
+        }
+    }
+    pub(super) fn water(&self, pos: &Coord) -> u8 {
+        if !self.grid.does_exist(pos) {
+            return 0;
+        }
+        return self.grid.tile(pos).water;
+    }
+    pub(super) fn near_water(&self, pos: &Coord) -> bool {
+        return self.neighbours(pos).iter().any(|c| self.water(c) > 0);
+    }
+    /// Food scent at `pos`, from 0 out of range to 1 on the food.
+    pub(super) fn scent(&self, pos: &Coord) -> f64 {
+        let radius = self.options.scent_radius;
+        if radius == 0 || !self.grid.does_exist(pos) {
+            return 0.0;
+        }
+        return self.grid.tile(pos).scent as f64 / radius.min(u8::MAX as usize) as f64;
+    }
+    pub(super) fn round(&self) -> usize {
+        return self.round_num;
+    }
+    pub fn clock(&self) -> Clock {
+        return Clock::new(self.round_num, &self.options);
+    }
+    /// Caves in underground open cells that lacked `support` solid neighbours on this and the
+    /// previous check, crushing any ant inside. Cells holding hives, food or a shaft never cave in.
+    fn settle(&mut self, support: usize) {
+        let mut collapsed = Vec::new();
+        for layer in self.nest_layer()..self.layers() {
+            for x in 0..self.cols() {
+                for y in 0..self.rows() {
+                    let c = Coord { x, y, layer };
+                    let held = self.grid.get(&c).elem.as_ref().map_or(false, |e| {
+                        let e = e.borrow();
+                        e.exists() && !e.is_removed() && e.type_elem() != ElementType::Ant
+                    });
+                    let tile = self.grid.tile(&c);
+                    if held || tile.is_solid() || tile.entrance {
+                        continue;
+                    }
+                    let (solid, fill) = self.grid.support(&c);
+                    let unstable = solid < support;
+                    if unstable && tile.unstable {
+                        collapsed.push((c, fill));
+                    }
+                    self.grid.tile_mut(&c).unstable = unstable;
+                }
+            }
+        }
+        for (c, terrain) in collapsed {
+            if let Some(elem) = self.grid.get(&c).elem.clone() {
+                if elem.borrow().type_elem() == ElementType::Ant && !elem.borrow().is_removed() {
+                    elem.borrow_mut().attacked(usize::MAX);
+                }
+            }
+            self.grid.get_mut(&c).elem = None;
+            self.grid.tile_mut(&c).fill(terrain);
+        }
+    }
     pub fn put<T: GridElement + 'static>(&mut self, elem: T) {
         self.put_raw(Rc::new(RefCell::new(elem)));
     }
@@ -236,9 +1159,53 @@ impl AntGrid {
         if self.is_blocked(&pos) {
             return;
         }
+        if self.is_dirt(&pos) {
+            self.grid.tile_mut(&pos).clear();
+        }
         self.grid.get_mut(&pos).elem = Some(elem_ref.clone());
         self.elements
             .insert(elem_ref.borrow().team_element(), elem_ref.clone());
+        if elem_ref.borrow().type_elem() == ElementType::Hive {
+            self.add_entrance(&pos);
+        }
+    }
+    /// Lays `terrain` on a cell unless an element stands there.
+    pub fn put_terrain(&mut self, pos: &Coord, terrain: Terrain) {
+        if !self.grid.does_exist(pos) || self.grid.get(pos).elem.is_some() {
+            return;
+        }
+        self.grid.tile_mut(pos).fill(terrain);
+    }
+    /// Pours `amount` units of water on an open cell.
+    pub fn put_water(&mut self, pos: &Coord, amount: u8) {
+        if !self.grid.does_exist(pos) || self.is_dirt(pos) {
+            return;
+        }
+        let tile = self.grid.tile_mut(pos);
+        tile.water = tile.water.saturating_add(amount).min(MAX_WATER);
+        self.wet.insert(*pos);
+    }
+    /// Places food sized by the options, protein or sugar by `protein_ratio`.
+    pub fn put_food(&mut self, pos: &Coord) {
+        let kind = if self.rng.gen_range(0..100) < self.options.protein_ratio {
+            FoodKind::Protein
+        } else {
+            FoodKind::Sugar
+        };
+        let food = Food::new(
+            pos,
+            self.options.food_amount,
+            self.options.food_quality,
+            kind,
+        );
+        self.put(food);
+    }
+    pub fn put_midden(&mut self, pos: &Coord) {
+        if !self.grid.does_exist(pos) || self.grid.tile(pos).midden {
+            return;
+        }
+        self.grid.tile_mut(pos).midden = true;
+        self.middens.push(pos.clone());
     }
 
     pub fn rows(&self) -> usize {
@@ -247,8 +1214,15 @@ impl AntGrid {
     pub fn cols(&self) -> usize {
         return self.grid.cols;
     }
+    pub fn layers(&self) -> usize {
+        return self.grid.layers;
+    }
+    /// Layer the nests are dug in, the surface itself on a single layer world.
+    pub fn nest_layer(&self) -> usize {
+        return 1.min(self.layers() - 1);
+    }
     pub fn export(&self, frames: usize, teams: Vec<Team>) -> Export {
-        return self.grid.export(frames, teams);
+        return self.grid.export(frames, teams, self.clock().tint());
     }
 }
 
@@ -257,3 +1231,187 @@ impl Display for AntGrid {
         write!(f, "{}", self.grid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Ant, Caste};
+    use image::Rgb;
+
+    fn team(id: usize) -> Team {
+        return Team {
+            color: Rgb([0, 0, 0]),
+            id,
+            health: 1,
+            name: format!("Team {}", id).into(),
+        };
+    }
+
+    fn coord(x: usize, y: usize) -> Coord {
+        return Coord { x, y, layer: 0 };
+    }
+
+    /// Worker of `team(1)` with three hit points at (2, 2), struck for two from (1, 2).
+    fn strike(flanker: Option<Coord>) -> AntGrid {
+        let mut grid = AntGrid::new(5, 5, 1);
+        let (attackers, defenders) = (team(0), team(1));
+        let target = coord(2, 2);
+        grid.put(Ant::new(
+            &target,
+            &Team {
+                health: 3,
+                ..defenders
+            },
+            Caste::Worker,
+        ));
+        grid.put(Ant::new(&coord(1, 2), &attackers, Caste::Worker));
+        if let Some(pos) = flanker {
+            grid.put(Ant::new(&pos, &attackers, Caste::Worker));
+        }
+        grid.attack(&coord(1, 2), &target, &attackers, 2);
+        return grid;
+    }
+
+    #[test]
+    fn lone_attacker_gets_no_flanking_bonus() {
+        let grid = strike(None);
+        assert!(grid.is_ant(&coord(2, 2)));
+        assert_eq!(grid.kills(&team(0)), 0);
+    }
+
+    #[test]
+    fn one_flanking_nestmate_adds_the_bonus() {
+        let grid = strike(Some(coord(3, 2)));
+        assert!(!grid.is_ant(&coord(2, 2)));
+        assert_eq!(grid.kills(&team(0)), 1);
+    }
+
+    fn total_water(grid: &AntGrid) -> usize {
+        let mut total = 0;
+        for x in 0..grid.cols() {
+            for y in 0..grid.rows() {
+                total += grid.water(&Coord { x, y, layer: 0 }) as usize;
+            }
+        }
+        return total;
+    }
+
+    #[test]
+    fn eaten_food_regrows_in_place() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        grid.options.food_regrow = 4;
+        grid.options.food_drop_rounds = 0;
+        let pos = coord(2, 2);
+        grid.put(Food::new(&pos, 2, 1, FoodKind::Sugar));
+        grid.run_round();
+        assert_eq!(grid.eat_food(&pos, 5), 2);
+        assert!(!grid.is_food(&pos));
+        for _ in 0..4 {
+            grid.run_round();
+        }
+        assert!(grid.is_food(&pos));
+    }
+
+    #[test]
+    fn eaten_food_is_cleared_without_regrowth() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        grid.options.food_drop_rounds = 0;
+        let pos = coord(2, 2);
+        grid.put(Food::new(&pos, 2, 1, FoodKind::Sugar));
+        grid.run_round();
+        grid.eat_food(&pos, 5);
+        grid.run_round();
+        assert!(!grid.is_blocked(&pos));
+    }
+
+    #[test]
+    fn put_water_is_capped() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        let pos = Coord {
+            x: 2,
+            y: 4,
+            layer: 0,
+        };
+        for _ in 0..3 {
+            grid.put_water(&pos, 4);
+        }
+        assert_eq!(grid.water(&pos), MAX_WATER);
+    }
+
+    #[test]
+    fn water_falls_before_spreading() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        grid.put_water(
+            &Coord {
+                x: 2,
+                y: 4,
+                layer: 0,
+            },
+            3,
+        );
+        grid.flow_water();
+        assert_eq!(
+            grid.water(&Coord {
+                x: 2,
+                y: 4,
+                layer: 0
+            }),
+            0
+        );
+        assert_eq!(
+            grid.water(&Coord {
+                x: 2,
+                y: 3,
+                layer: 0
+            }),
+            3
+        );
+        assert_eq!(total_water(&grid), 3);
+    }
+
+    #[test]
+    fn water_into_full_cell_keeps_depth() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        let top = Coord {
+            x: 2,
+            y: 4,
+            layer: 0,
+        };
+        let below = Coord {
+            x: 2,
+            y: 3,
+            layer: 0,
+        };
+        for _ in 0..3 {
+            grid.put_water(&below, 4);
+            grid.put_water(&top, 4);
+        }
+        for _ in 0..10 {
+            grid.flow_water();
+        }
+        assert!(grid.water(&below) <= MAX_WATER);
+        assert_eq!(total_water(&grid), 2 * MAX_WATER as usize);
+    }
+
+    #[test]
+    fn water_stops_at_dirt() {
+        let mut grid = AntGrid::new(5, 5, 1);
+        let below = Coord {
+            x: 2,
+            y: 3,
+            layer: 0,
+        };
+        grid.put_terrain(&below, Terrain::Dirt);
+        grid.put_water(
+            &Coord {
+                x: 2,
+                y: 4,
+                layer: 0,
+            },
+            2,
+        );
+        grid.flow_water();
+        assert_eq!(grid.water(&below), 0);
+        assert_eq!(total_water(&grid), 2);
+    }
+}
@@ -0,0 +1,182 @@
+use std::f64::consts::PI;
+
+use strum_macros::EnumIter;
+
+use super::Options;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub(crate) enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Percentage of the usual food drops that still happen.
+    pub fn food_factor(&self) -> usize {
+        match self {
+            Season::Spring => 150,
+            Season::Summer => 100,
+            Season::Autumn => 50,
+            Season::Winter => 0,
+        }
+    }
+    /// Percentage of the usual pheromone lifetime, heat makes trails fade faster.
+    pub fn decay_factor(&self) -> usize {
+        match self {
+            Season::Spring | Season::Autumn => 100,
+            Season::Summer => 50,
+            Season::Winter => 200,
+        }
+    }
+    fn hue(&self) -> [f64; 3] {
+        match self {
+            Season::Spring => [0.95, 1.05, 0.95],
+            Season::Summer => [1.05, 1.0, 0.9],
+            Season::Autumn => [1.05, 0.95, 0.85],
+            Season::Winter => [0.9, 0.95, 1.1],
+        }
+    }
+}
+
+/// Time of day and season of a round.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Clock {
+    /// Fraction of the day gone, the first half is daytime.
+    phase: f64,
+    season: Option<Season>,
+}
+
+impl Clock {
+    pub fn new(round: usize, options: &Options) -> Self {
+        if options.day_length == 0 {
+            return Clock {
+                phase: 0.25,
+                season: None,
+            };
+        }
+        let day = round / options.day_length;
+        let season = match options.year_length {
+            0 => None,
+            year => {
+                let quarter = (day % year) * 4 / year;
+                Some(
+                    [
+                        Season::Spring,
+                        Season::Summer,
+                        Season::Autumn,
+                        Season::Winter,
+                    ][quarter],
+                )
+            }
+        };
+        return Clock {
+            phase: (round % options.day_length) as f64 / options.day_length as f64,
+            season,
+        };
+    }
+    /// Sunlight from 0 at night to 1 at midday.
+    pub fn daylight(&self) -> f64 {
+        return (2.0 * PI * self.phase).sin().max(0.0);
+    }
+    pub fn is_night(&self) -> bool {
+        return self.phase >= 0.5;
+    }
+    pub fn season(&self) -> Option<Season> {
+        return self.season;
+    }
+    /// Percentage chance an idle ant goes out this round.
+    pub fn activity(&self, options: &Options) -> usize {
+        if self.season == Some(Season::Winter) {
+            return options.winter_activity;
+        }
+        if self.is_night() {
+            return options.night_activity;
+        }
+        return 100;
+    }
+    pub fn food_factor(&self) -> usize {
+        return self.season.map_or(100, |s| s.food_factor());
+    }
+    /// Percentage of the usual pheromone lifetime, trails last longer in the cool of night.
+    pub fn decay_factor(&self) -> usize {
+        let night = if self.is_night() { 150 } else { 100 };
+        return self.season.map_or(100, |s| s.decay_factor()) * night / 100;
+    }
+    /// Colour multipliers for the surface, darker at night and hued by season.
+    pub fn tint(&self) -> [f64; 3] {
+        let brightness = 0.35 + 0.65 * self.daylight();
+        let hue = self.season.map_or([1.0; 3], |s| s.hue());
+        return hue.map(|h| h * brightness);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(day_length: usize, year_length: usize) -> Options {
+        return Options {
+            day_length,
+            year_length,
+            ..Options::default()
+        };
+    }
+
+    #[test]
+    fn no_day_cycle_is_always_midday() {
+        let options = options(0, 4);
+        for round in [0, 1, 999] {
+            let clock = Clock::new(round, &options);
+            assert_eq!(clock.daylight(), 1.0);
+            assert!(!clock.is_night());
+            assert_eq!(clock.season(), None);
+            assert_eq!(clock.activity(&options), 100);
+        }
+    }
+
+    #[test]
+    fn second_half_of_the_day_is_night() {
+        let options = options(100, 0);
+        assert!(!Clock::new(0, &options).is_night());
+        assert!(!Clock::new(49, &options).is_night());
+        assert!(Clock::new(50, &options).is_night());
+        assert!(Clock::new(99, &options).is_night());
+        assert!(!Clock::new(100, &options).is_night());
+        assert_eq!(
+            Clock::new(50, &options).activity(&options),
+            options.night_activity
+        );
+    }
+
+    #[test]
+    fn no_year_has_no_season() {
+        assert_eq!(Clock::new(12345, &options(10, 0)).season(), None);
+    }
+
+    #[test]
+    fn seasons_change_every_quarter_year() {
+        let options = options(10, 8);
+        let season = |day: usize| Clock::new(day * 10, &options).season();
+        assert_eq!(season(0), Some(Season::Spring));
+        assert_eq!(season(1), Some(Season::Spring));
+        assert_eq!(season(2), Some(Season::Summer));
+        assert_eq!(season(3), Some(Season::Summer));
+        assert_eq!(season(4), Some(Season::Autumn));
+        assert_eq!(season(6), Some(Season::Winter));
+        assert_eq!(season(7), Some(Season::Winter));
+        assert_eq!(season(8), Some(Season::Spring));
+        assert_eq!(Clock::new(19, &options).season(), Some(Season::Spring));
+        assert_eq!(Clock::new(20, &options).season(), Some(Season::Summer));
+    }
+
+    #[test]
+    fn winter_activity_overrides_daytime() {
+        let options = options(10, 4);
+        let clock = Clock::new(30, &options);
+        assert_eq!(clock.season(), Some(Season::Winter));
+        assert!(!clock.is_night());
+        assert_eq!(clock.activity(&options), options.winter_activity);
+    }
+}
@@ -0,0 +1,10 @@
+/// Loads a team has brought home, split by whether they changed hands on the way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Deliveries {
+    /// Loads carried all the way by the ant that picked them up.
+    pub direct: usize,
+    /// Loads handed along a relay at least once.
+    pub relayed: usize,
+    /// Handovers between nestmates.
+    pub handoffs: usize,
+}
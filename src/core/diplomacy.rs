@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use strum_macros::EnumIter;
+
+/// How two teams treat each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub(crate) enum Relation {
+    /// Never fight and answer each other's battle calls.
+    Allied,
+    /// Leave each other alone.
+    Neutral,
+    Hostile,
+}
+
+impl Relation {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        return match name.to_lowercase().as_str() {
+            "allied" => Ok(Relation::Allied),
+            "neutral" => Ok(Relation::Neutral),
+            "hostile" => Ok(Relation::Hostile),
+            _ => Err(format!("Invalid relation {:?}", name)),
+        };
+    }
+}
+
+/// A relation two teams take up at `round`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Treaty {
+    pub teams: (usize, usize),
+    pub relation: Relation,
+    pub round: usize,
+}
+
+/// Relations between every pair of teams, hostile unless set otherwise.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Diplomacy {
+    relations: HashMap<(usize, usize), Relation>,
+    /// Treaties still to come into force, in no particular order.
+    treaties: Vec<Treaty>,
+}
+
+impl Diplomacy {
+    fn key(a: usize, b: usize) -> (usize, usize) {
+        return (a.min(b), a.max(b));
+    }
+    pub fn relation(&self, a: usize, b: usize) -> Relation {
+        if a == b {
+            return Relation::Allied;
+        }
+        return self
+            .relations
+            .get(&Diplomacy::key(a, b))
+            .copied()
+            .unwrap_or(Relation::Hostile);
+    }
+    pub fn set(&mut self, a: usize, b: usize, relation: Relation) {
+        self.relations.insert(Diplomacy::key(a, b), relation);
+    }
+    pub fn schedule(&mut self, treaty: Treaty) {
+        self.treaties.push(treaty);
+    }
+    /// Puts the treaties due by `round` into force.
+    pub fn advance(&mut self, round: usize) {
+        let due: Vec<Treaty> = self
+            .treaties
+            .iter()
+            .filter(|t| t.round <= round)
+            .cloned()
+            .collect();
+        self.treaties.retain(|t| t.round > round);
+        for treaty in due {
+            self.set(treaty.teams.0, treaty.teams.1, treaty.relation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teams_start_hostile_and_allied_with_themselves() {
+        let diplomacy = Diplomacy::default();
+        assert_eq!(diplomacy.relation(0, 1), Relation::Hostile);
+        assert_eq!(diplomacy.relation(2, 2), Relation::Allied);
+    }
+
+    #[test]
+    fn relations_are_symmetric() {
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.set(2, 0, Relation::Neutral);
+        assert_eq!(diplomacy.relation(0, 2), Relation::Neutral);
+        assert_eq!(diplomacy.relation(2, 0), Relation::Neutral);
+        assert_eq!(diplomacy.relation(0, 1), Relation::Hostile);
+    }
+
+    #[test]
+    fn treaties_take_effect_at_their_round() {
+        let mut diplomacy = Diplomacy::default();
+        diplomacy.schedule(Treaty {
+            teams: (0, 1),
+            relation: Relation::Allied,
+            round: 10,
+        });
+        diplomacy.schedule(Treaty {
+            teams: (1, 0),
+            relation: Relation::Hostile,
+            round: 20,
+        });
+        diplomacy.advance(9);
+        assert_eq!(diplomacy.relation(0, 1), Relation::Hostile);
+        diplomacy.advance(10);
+        assert_eq!(diplomacy.relation(0, 1), Relation::Allied);
+        diplomacy.advance(19);
+        assert_eq!(diplomacy.relation(1, 0), Relation::Allied);
+        diplomacy.advance(25);
+        assert_eq!(diplomacy.relation(1, 0), Relation::Hostile);
+    }
+
+    #[test]
+    fn parse_relation_names() {
+        assert_eq!(Relation::parse("Allied"), Ok(Relation::Allied));
+        assert_eq!(Relation::parse("neutral"), Ok(Relation::Neutral));
+        assert_eq!(Relation::parse("HOSTILE"), Ok(Relation::Hostile));
+        assert!(Relation::parse("friendly").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+/// Course of the colony disease in one ant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Infection {
+    #[default]
+    Susceptible,
+    /// Sick, recovering once `rounds` run out.
+    Infected { rounds: usize },
+    /// Immune, susceptible again once `rounds` run out.
+    Recovered { rounds: usize },
+}
+
+impl Infection {
+    pub fn is_infected(&self) -> bool {
+        return matches!(self, Infection::Infected { .. });
+    }
+    /// Advances the disease by one round.
+    pub fn tick(self, immunity_rounds: usize) -> Self {
+        return match self {
+            Infection::Susceptible => self,
+            Infection::Infected { rounds: 0 } => Infection::Recovered {
+                rounds: immunity_rounds,
+            },
+            Infection::Infected { rounds } => Infection::Infected { rounds: rounds - 1 },
+            Infection::Recovered { rounds: 0 } => Infection::Susceptible,
+            Infection::Recovered { rounds } => Infection::Recovered { rounds: rounds - 1 },
+        };
+    }
+}
+
+/// Living ants of one team in each disease compartment at a given round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Sir {
+    pub round: usize,
+    pub susceptible: usize,
+    pub infected: usize,
+    pub recovered: usize,
+}
+
+impl Sir {
+    pub fn count(&mut self, infection: Infection) {
+        match infection {
+            Infection::Susceptible => self.susceptible += 1,
+            Infection::Infected { .. } => self.infected += 1,
+            Infection::Recovered { .. } => self.recovered += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn susceptible_stays_healthy() {
+        assert_eq!(Infection::Susceptible.tick(10), Infection::Susceptible);
+    }
+
+    #[test]
+    fn sickness_counts_down_then_grants_immunity() {
+        let mut infection = Infection::Infected { rounds: 2 };
+        infection = infection.tick(5);
+        assert_eq!(infection, Infection::Infected { rounds: 1 });
+        infection = infection.tick(5).tick(5);
+        assert_eq!(infection, Infection::Recovered { rounds: 5 });
+        assert!(!infection.is_infected());
+    }
+
+    #[test]
+    fn immunity_wears_off() {
+        let mut infection = Infection::Infected { rounds: 0 };
+        for _ in 0..3 {
+            infection = infection.tick(1);
+        }
+        assert_eq!(infection, Infection::Susceptible);
+    }
+
+    #[test]
+    fn sir_counts_each_compartment() {
+        let mut sir = Sir::default();
+        sir.count(Infection::Susceptible);
+        sir.count(Infection::Infected { rounds: 3 });
+        sir.count(Infection::Infected { rounds: 0 });
+        sir.count(Infection::Recovered { rounds: 1 });
+        assert_eq!((sir.susceptible, sir.infected, sir.recovered), (1, 2, 1));
+    }
+}
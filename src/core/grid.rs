@@ -3,8 +3,12 @@ mod coord;
 mod export;
 mod grid;
 mod team;
+mod terrain;
+mod tile;
 pub(crate) use cell::{Cell, Pheromones};
 pub(crate) use coord::{Coord, Dir};
 pub(crate) use export::Export;
 pub(crate) use grid::Grid;
-pub(crate) use team::Team;
+pub(crate) use team::{Team, TeamConfig};
+pub(crate) use terrain::Terrain;
+pub(crate) use tile::Tile;
@@ -4,7 +4,7 @@ use image::Rgb;
 
 use crate::core::grid_elements::{empty::Empty, grid_element::GridElement};
 
-use super::Team;
+use super::{Team, Tile};
 
 #[derive(Clone)]
 pub(crate) struct Pheromones {
@@ -37,10 +37,22 @@ impl Cell {
             .clone()
             .unwrap_or(Rc::new(RefCell::new(Empty::new())));
     }
-    pub fn color(&self) -> Rgb<u8> {
+    pub fn color(&self, tile: &Tile) -> Rgb<u8> {
         match &self.elem {
             Some(elem) => elem.borrow().color(),
             None => {
+                if let Some(terrain) = tile.terrain() {
+                    return terrain.color();
+                }
+                if tile.water > 0 {
+                    return Rgb([0, 40, 100 + 19 * tile.water.min(8)]);
+                }
+                if tile.entrance {
+                    return Rgb([120, 90, 60]);
+                }
+                if tile.midden {
+                    return Rgb([60, 60, 20]);
+                }
                 return if self.pheromones.len() > 0 {
                     Rgb([10, 10, 10])
                 } else {
@@ -49,9 +61,9 @@ impl Cell {
             }
         }
     }
-    pub fn clear_old(&mut self, round_num: usize, decay: usize) {
+    pub fn clear_old(&mut self, round_num: usize, decay: impl Fn(&Team) -> usize) {
         self.pheromones
-            .drain_filter(|_, val| round_num - val.age > decay);
+            .drain_filter(|(team, _), val| round_num - val.age > decay(team));
     }
 }
 
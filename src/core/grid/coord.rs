@@ -10,8 +10,11 @@ pub(crate) enum Dir {
 pub(crate) struct Coord {
     pub x: usize,
     pub y: usize,
+    /// 0 is the surface, higher layers lie further underground.
+    pub layer: usize,
 }
 impl Coord {
+    /// Straight-line distance within a layer, the layers themselves are ignored.
     pub fn distance(&self, other: &Coord) -> f64 {
         return (((other.x.abs_diff(self.x)).pow(2) + (other.y.abs_diff(self.y)).pow(2)) as f64)
             .sqrt();
@@ -21,10 +24,12 @@ impl Coord {
             Dir::UP => Some(Coord {
                 x: self.x,
                 y: self.y + 1,
+                layer: self.layer,
             }),
             Dir::LEFT => Some(Coord {
                 x: self.x + 1,
                 y: self.y,
+                layer: self.layer,
             }),
             Dir::DOWN => {
                 if self.y == 0 {
@@ -33,6 +38,7 @@ impl Coord {
                 Some(Coord {
                     x: self.x,
                     y: self.y - 1,
+                    layer: self.layer,
                 })
             }
             Dir::RIGHT => {
@@ -42,6 +48,7 @@ impl Coord {
                 Some(Coord {
                     x: self.x - 1,
                     y: self.y,
+                    layer: self.layer,
                 })
             }
         };
@@ -8,23 +8,30 @@ pub(crate) struct Export {
     colors: Vec<Vec<Rgb<u8>>>,
     rows: usize,
     cols: usize,
+    layers: usize,
     frames: usize,
     teams: Vec<Team>,
+    /// Colour multipliers for the surface layer.
+    tint: [f64; 3],
 }
 impl Export {
     pub fn new(
         colors: Vec<Vec<Rgb<u8>>>,
         rows: usize,
         cols: usize,
+        layers: usize,
         frames: usize,
         teams: Vec<Team>,
+        tint: [f64; 3],
     ) -> Self {
         Export {
             colors,
             rows,
             cols,
+            layers,
             frames,
             teams,
+            tint,
         }
     }
     pub(crate) fn frames(&self) -> usize {
@@ -33,19 +40,37 @@ impl Export {
     pub(crate) fn teams(&self) -> &Vec<Team> {
         return &self.teams;
     }
-    pub fn to_image(&self) -> ColorImage {
+    pub(crate) fn layers(&self) -> usize {
+        return self.layers;
+    }
+    /// Renders one layer, or every layer side by side with the surface on the left.
+    pub fn to_image(&self, layer: Option<usize>) -> ColorImage {
         const COLORS: usize = 4;
-        let mut image_buffer: Vec<u8> = vec![0; self.rows * self.cols * COLORS];
-        for (x, row) in self.colors.iter().enumerate() {
+        let (first, columns) = match layer {
+            Some(layer) => (
+                layer * self.cols,
+                &self.colors[layer * self.cols..(layer + 1) * self.cols],
+            ),
+            None => (0, &self.colors[..]),
+        };
+        let width = columns.len();
+        let mut image_buffer: Vec<u8> = vec![0; self.rows * width * COLORS];
+        for (x, row) in columns.iter().enumerate() {
+            let surface = first + x < self.cols;
             for (y, color) in row.iter().enumerate() {
-                let color_a = color.to_rgba();
-                let idx = COLORS * (x + y * self.cols);
+                let mut color_a = color.to_rgba();
+                if surface {
+                    for i in 0..3 {
+                        color_a[i] = (color_a[i] as f64 * self.tint[i]).min(255.0) as u8;
+                    }
+                }
+                let idx = COLORS * (x + y * width);
                 for i in 0..COLORS {
                     image_buffer[idx + i] = color_a[i];
                 }
             }
         }
-        let size = [self.cols as _, self.rows as _];
+        let size = [width as _, self.rows as _];
         return ColorImage::from_rgba_unmultiplied(size, image_buffer.as_slice());
     }
 }
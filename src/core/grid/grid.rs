@@ -1,55 +1,120 @@
 use std::fmt::Display;
 
-use super::{Cell, Coord, Export, Team};
+use super::{Cell, Coord, Export, Team, Terrain, Tile};
 #[derive(Clone)]
 pub(crate) struct Grid {
+    /// Columns of every layer in turn, so layer `l` starts at column `l * cols`.
     pub grid: Vec<Vec<Cell>>,
+    tiles: Vec<Tile>,
     pub rows: usize,
     pub cols: usize,
+    pub layers: usize,
 }
 impl Grid {
-    pub fn new(rows: usize, cols: usize) -> Self {
-        let mut grid = Vec::with_capacity(cols);
-        for x in 0..cols {
+    pub fn new(rows: usize, cols: usize, layers: usize) -> Self {
+        let mut grid = Vec::with_capacity(cols * layers);
+        for x in 0..cols * layers {
             grid.push(Vec::with_capacity(rows));
             for _ in 0..rows {
                 grid.get_mut(x).unwrap().push(Cell::default());
             }
         }
-        return Grid { grid, rows, cols };
+        let tiles = vec![Tile::default(); rows * cols * layers];
+        return Grid {
+            grid,
+            tiles,
+            rows,
+            cols,
+            layers,
+        };
+    }
+    fn column(&self, c: &Coord) -> usize {
+        return c.layer * self.cols + c.x;
     }
     pub fn get(&self, c: &Coord) -> &Cell {
         assert!(self.does_exist(c));
-        return self.grid.get(c.x).unwrap().get(c.y).unwrap();
+        return self.grid.get(self.column(c)).unwrap().get(c.y).unwrap();
     }
     pub fn get_mut(&mut self, c: &Coord) -> &mut Cell {
         assert!(self.does_exist(c));
-        return self.grid.get_mut(c.x).unwrap().get_mut(c.y).unwrap();
+        let column = self.column(c);
+        return self.grid.get_mut(column).unwrap().get_mut(c.y).unwrap();
+    }
+    pub fn tile(&self, c: &Coord) -> &Tile {
+        assert!(self.does_exist(c));
+        return &self.tiles[self.column(c) * self.rows + c.y];
+    }
+    pub fn tile_mut(&mut self, c: &Coord) -> &mut Tile {
+        assert!(self.does_exist(c));
+        let column = self.column(c);
+        return &mut self.tiles[column * self.rows + c.y];
+    }
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile> {
+        return self.tiles.iter_mut();
     }
     pub fn does_exist(&self, coord: &Coord) -> bool {
-        if coord.x >= self.cols || coord.y >= self.rows {
+        if coord.x >= self.cols || coord.y >= self.rows || coord.layer >= self.layers {
             return false;
         }
         return true;
     }
-    pub fn export(&self, frames: usize, teams: Vec<Team>) -> Export {
+    /// Counts the solid tiles around `c`, with the map edge counting as solid, and picks the
+    /// terrain that would fall in if it caved in, preferring the tile above.
+    pub fn support(&self, c: &Coord) -> (usize, Terrain) {
+        let mut solid = 0;
+        let mut fill = None;
+        for (dx, dy) in [
+            (0, 1),
+            (-1, 1),
+            (1, 1),
+            (-1, 0),
+            (1, 0),
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+        ] {
+            let (x, y) = (c.x as isize + dx, c.y as isize + dy);
+            if x < 0 || y < 0 || x as usize >= self.cols || y as usize >= self.rows {
+                solid += 1;
+                continue;
+            }
+            let tile = self.tile(&Coord {
+                x: x as usize,
+                y: y as usize,
+                layer: c.layer,
+            });
+            if tile.is_solid() {
+                solid += 1;
+                fill = fill.or(tile.terrain());
+            }
+        }
+        return (solid, fill.unwrap_or(Terrain::Dirt));
+    }
+    pub fn export(&self, frames: usize, teams: Vec<Team>, tint: [f64; 3]) -> Export {
         let data = self
             .grid
             .iter()
-            .map(|r| r.iter().map(|c| c.color()).collect())
+            .zip(self.tiles.chunks(self.rows))
+            .map(|(r, tiles)| r.iter().zip(tiles).map(|(c, t)| c.color(t)).collect())
             .collect();
-        return Export::new(data, self.rows, self.cols, frames, teams);
+        return Export::new(data, self.rows, self.cols, self.layers, frames, teams, tint);
     }
 }
 impl Display for Grid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for i in 0..self.rows {
             let y = self.rows - i - 1;
-            write!(f, "|")?;
-            for x in 0..self.cols {
-                write!(f, " ")?;
-                write!(f, "{}", self.get(&Coord { x, y }))?;
-                write!(f, " |")?;
+            for layer in 0..self.layers {
+                write!(f, "|")?;
+                for x in 0..self.cols {
+                    let c = Coord { x, y, layer };
+                    write!(f, " ")?;
+                    match (&self.get(&c).elem, self.tile(&c).terrain()) {
+                        (None, Some(terrain)) => write!(f, "{}", terrain.symbol())?,
+                        _ => write!(f, "{}", self.get(&c))?,
+                    }
+                    write!(f, " |")?;
+                }
             }
             write!(f, "\n")?;
         }
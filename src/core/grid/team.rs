@@ -1,19 +1,258 @@
+use std::sync::Arc;
+
 use colored::Color;
 use image::Rgb;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use crate::core::Relation;
+
+use super::Coord;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct Team {
     pub color: Rgb<u8>,
     pub id: usize,
     pub health: usize,
-    pub name: &'static str,
+    /// Shared so cloning a team for every element and pheromone key does not allocate.
+    pub name: Arc<str>,
 }
-impl Into<Color> for Team {
-    fn into(self) -> Color {
+impl From<&Team> for Color {
+    fn from(team: &Team) -> Self {
         return Color::TrueColor {
-            r: self.color.0[0],
-            g: self.color.0[1],
-            b: self.color.0[2],
+            r: team.color.0[0],
+            g: team.color.0[1],
+            b: team.color.0[2],
+        };
+    }
+}
+
+/// Setup parameters for one team, used to build a [`Team`] and its starting hives on reset.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TeamConfig {
+    pub name: String,
+    pub color: Rgb<u8>,
+    /// Overrides the team's `starting_food` option when set.
+    pub starting_food: Option<usize>,
+    /// Starting hive positions on the nest layer, positions off the grid are dropped and a
+    /// random one is picked when none remain.
+    pub hives: Vec<Coord>,
+    /// Relations to other teams by name, each taken up at the given round.
+    pub relations: Vec<(String, Relation, usize)>,
+}
+
+impl TeamConfig {
+    pub fn new(name: &str, color: Rgb<u8>) -> Self {
+        return TeamConfig {
+            name: name.to_string(),
+            color,
+            starting_food: None,
+            hives: Vec::new(),
+            relations: Vec::new(),
         };
     }
+    pub fn default_roster() -> Vec<TeamConfig> {
+        return vec![
+            TeamConfig::new("Red", Rgb([255, 0, 0])),
+            TeamConfig::new("Purple", Rgb([255, 0, 255])),
+            TeamConfig::new("Yellow", Rgb([255, 255, 0])),
+        ];
+    }
+    /// Parses `name:rrggbb[:food[:x,y;x,y...]]`, an empty food field keeps the team option.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut fields = spec.trim().split(':');
+        let name = fields.next().filter(|n| !n.is_empty());
+        let name = name.ok_or(format!("Missing team name in {:?}", spec))?;
+        let hex = fields
+            .next()
+            .ok_or(format!("Missing colour in {:?}", spec))?;
+        let mut config = TeamConfig::new(name, parse_color(hex)?);
+        if let Some(food) = fields.next().filter(|f| !f.is_empty()) {
+            let food = food
+                .parse()
+                .map_err(|_| format!("Invalid starting food {:?}", food))?;
+            config.starting_food = Some(food);
+        }
+        if let Some(hives) = fields.next() {
+            for hive in hives.split(';').filter(|h| !h.is_empty()) {
+                config.hives.push(parse_coord(hive)?);
+            }
+        }
+        if fields.next().is_some() {
+            return Err(format!("Too many fields in {:?}", spec));
+        }
+        return Ok(config);
+    }
+    /// Parses a roster file with one team per line, skipping blank lines and `#` comments.
+    pub fn parse_roster(contents: &str) -> Result<Vec<TeamConfig>, String> {
+        return contents
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(TeamConfig::parse)
+            .collect();
+    }
+    /// Builds the roster from `--team <spec>` and `--teams <file>` arguments, then applies
+    /// `--relation <team>:<team>:<allied|neutral|hostile>[:round]` arguments to it.
+    pub fn from_args(args: &[String]) -> Result<Vec<TeamConfig>, String> {
+        let mut roster = Vec::new();
+        let mut relations = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--team" => {
+                    let spec = args.next().ok_or("--team needs a team spec")?;
+                    roster.push(TeamConfig::parse(spec)?);
+                }
+                "--teams" => {
+                    let path = args.next().ok_or("--teams needs a file")?;
+                    let contents = std::fs::read_to_string(path)
+                        .map_err(|e| format!("Cannot read {:?}: {}", path, e))?;
+                    roster.extend(TeamConfig::parse_roster(&contents)?);
+                }
+                "--relation" => {
+                    relations.push(args.next().ok_or("--relation needs a relation spec")?);
+                }
+                _ => (),
+            }
+        }
+        if roster.is_empty() {
+            roster = TeamConfig::default_roster();
+        }
+        for spec in relations {
+            let (team, other, relation, round) = parse_relation(spec)?;
+            if !roster.iter().any(|c| c.name == other) {
+                return Err(format!("Unknown team {:?} in {:?}", other, spec));
+            }
+            let config = roster.iter_mut().find(|c| c.name == team);
+            let config = config.ok_or(format!("Unknown team {:?} in {:?}", team, spec))?;
+            config.relations.push((other, relation, round));
+        }
+        return Ok(roster);
+    }
+}
+
+fn parse_color(hex: &str) -> Result<Rgb<u8>, String> {
+    let hex = hex.trim_start_matches('#');
+    let value = u32::from_str_radix(hex, 16).map_err(|_| format!("Invalid colour {:?}", hex))?;
+    if hex.len() != 6 {
+        return Err(format!("Invalid colour {:?}", hex));
+    }
+    return Ok(Rgb([(value >> 16) as u8, (value >> 8) as u8, value as u8]));
+}
+
+/// Parses `team:other:relation[:round]`, the relation holds from the start without a round.
+fn parse_relation(spec: &str) -> Result<(String, String, Relation, usize), String> {
+    let fields: Vec<&str> = spec.trim().split(':').collect();
+    if fields.len() < 3 || fields.len() > 4 {
+        return Err(format!("Invalid relation {:?}", spec));
+    }
+    let round = match fields.get(3) {
+        None => 0,
+        Some(round) => round
+            .parse()
+            .map_err(|_| format!("Invalid round {:?}", round))?,
+    };
+    return Ok((
+        fields[0].to_string(),
+        fields[1].to_string(),
+        Relation::parse(fields[2])?,
+        round,
+    ));
+}
+
+fn parse_coord(pos: &str) -> Result<Coord, String> {
+    let (x, y) = pos
+        .split_once(',')
+        .ok_or(format!("Invalid hive position {:?}", pos))?;
+    let parse = |v: &str| {
+        v.trim()
+            .parse()
+            .map_err(|_| format!("Invalid hive position {:?}", pos))
+    };
+    return Ok(Coord {
+        x: parse(x)?,
+        y: parse(y)?,
+        layer: 0,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_name_and_colour() {
+        let config = TeamConfig::parse("Blue:#0000ff").unwrap();
+        assert_eq!(config, TeamConfig::new("Blue", Rgb([0, 0, 255])));
+    }
+
+    #[test]
+    fn parse_food_and_hives() {
+        let config = TeamConfig::parse(" Green:00ff00:50:3,4;10, 2 ").unwrap();
+        assert_eq!(config.starting_food, Some(50));
+        assert_eq!(
+            config.hives,
+            vec![
+                Coord {
+                    x: 3,
+                    y: 4,
+                    layer: 0
+                },
+                Coord {
+                    x: 10,
+                    y: 2,
+                    layer: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty_food_keeps_option() {
+        let config = TeamConfig::parse("Green:00ff00::1,1").unwrap();
+        assert_eq!(config.starting_food, None);
+        assert_eq!(config.hives.len(), 1);
+    }
+
+    #[test]
+    fn parse_rejects_bad_specs() {
+        assert!(TeamConfig::parse("").is_err());
+        assert!(TeamConfig::parse(":ff0000").is_err());
+        assert!(TeamConfig::parse("Red").is_err());
+        assert!(TeamConfig::parse("Red:ff00").is_err());
+        assert!(TeamConfig::parse("Red:gg0000").is_err());
+        assert!(TeamConfig::parse("Red:ff0000:lots").is_err());
+        assert!(TeamConfig::parse("Red:ff0000:5:1;2").is_err());
+        assert!(TeamConfig::parse("Red:ff0000:5:1,2:extra").is_err());
+    }
+
+    #[test]
+    fn parse_roster_skips_comments() {
+        let roster = TeamConfig::parse_roster("# teams\nRed:ff0000\n\n  Blue:0000ff\n").unwrap();
+        let names: Vec<&str> = roster.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Red", "Blue"]);
+    }
+
+    #[test]
+    fn parse_relation_with_round() {
+        assert_eq!(
+            parse_relation("Red:Blue:allied:200"),
+            Ok(("Red".to_string(), "Blue".to_string(), Relation::Allied, 200))
+        );
+    }
+
+    #[test]
+    fn parse_relation_starts_at_zero() {
+        assert_eq!(
+            parse_relation("Red:Blue:Neutral"),
+            Ok(("Red".to_string(), "Blue".to_string(), Relation::Neutral, 0))
+        );
+    }
+
+    #[test]
+    fn parse_relation_rejects_bad_specs() {
+        assert!(parse_relation("Red:Blue").is_err());
+        assert!(parse_relation("Red:Blue:friends").is_err());
+        assert!(parse_relation("Red:Blue:allied:soon").is_err());
+        assert!(parse_relation("Red:Blue:allied:1:2").is_err());
+    }
 }
@@ -0,0 +1,58 @@
+use image::Rgb;
+use strum_macros::EnumIter;
+
+#[derive(Debug, EnumIter, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub(crate) enum Terrain {
+    Dirt,
+    Sand,
+    Clay,
+    Mud,
+    Rock,
+}
+
+impl Terrain {
+    /// Hits needed to dig through, `None` if it cannot be dug.
+    pub fn dig_cost(&self) -> Option<usize> {
+        match self {
+            Terrain::Mud => Some(0),
+            Terrain::Dirt | Terrain::Sand => Some(1),
+            Terrain::Clay => Some(3),
+            Terrain::Rock => None,
+        }
+    }
+    /// Solid terrain has to be dug out before an ant can enter, the rest stays underfoot.
+    pub fn is_solid(&self) -> bool {
+        return self.dig_cost() != Some(0);
+    }
+    /// Rounds an ant pauses after entering.
+    pub fn traversal_cost(&self) -> usize {
+        match self {
+            Terrain::Sand => 0,
+            Terrain::Dirt | Terrain::Clay => 1,
+            Terrain::Mud => 3,
+            Terrain::Rock => 0,
+        }
+    }
+    /// Total rounds spent getting through, `None` if impassable.
+    pub fn effort(&self) -> Option<usize> {
+        return self.dig_cost().map(|dig| dig + self.traversal_cost());
+    }
+    pub fn color(&self) -> Rgb<u8> {
+        match self {
+            Terrain::Dirt => Rgb([48, 23, 0]),
+            Terrain::Sand => Rgb([120, 100, 60]),
+            Terrain::Clay => Rgb([110, 50, 30]),
+            Terrain::Mud => Rgb([40, 35, 20]),
+            Terrain::Rock => Rgb([90, 90, 90]),
+        }
+    }
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Terrain::Dirt => "x",
+            Terrain::Sand => ".",
+            Terrain::Clay => "#",
+            Terrain::Mud => "~",
+            Terrain::Rock => "R",
+        }
+    }
+}
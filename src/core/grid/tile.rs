@@ -0,0 +1,64 @@
+use super::Terrain;
+
+/// Static ground of one cell, stored apart from the elements so filling a grid stays cheap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Tile {
+    terrain: Option<Terrain>,
+    hits: u8,
+    pub midden: bool,
+    /// Set when the last stability check found the open tile unsupported.
+    pub unstable: bool,
+    /// Links the tile to the same spot on the neighbouring layers.
+    pub entrance: bool,
+    /// Food scent, strongest on the food and fading by one per step away from it.
+    pub scent: u8,
+    /// Depth of standing water, 0 when dry.
+    pub water: u8,
+}
+
+impl Tile {
+    pub fn new(terrain: Terrain) -> Self {
+        return Tile {
+            terrain: Some(terrain),
+            hits: terrain.dig_cost().unwrap_or(0) as u8,
+            midden: false,
+            unstable: false,
+            entrance: false,
+            scent: 0,
+            water: 0,
+        };
+    }
+    /// Covers the tile with `terrain`, keeping its midden and entrance markings.
+    pub fn fill(&mut self, terrain: Terrain) {
+        *self = Tile {
+            midden: self.midden,
+            entrance: self.entrance,
+            ..Tile::new(terrain)
+        };
+    }
+    pub fn clear(&mut self) {
+        self.terrain = None;
+        self.hits = 0;
+    }
+    pub fn terrain(&self) -> Option<Terrain> {
+        return self.terrain;
+    }
+    pub fn is_solid(&self) -> bool {
+        return self.terrain.map_or(false, |t| t.is_solid());
+    }
+    /// Digs once, returning whether the tile can now be entered.
+    pub fn dig(&mut self) -> bool {
+        if !self.is_solid() {
+            return true;
+        }
+        if self.terrain.and_then(|t| t.dig_cost()).is_none() {
+            return false;
+        }
+        self.hits = self.hits.saturating_sub(1);
+        if self.hits == 0 {
+            self.clear();
+            return true;
+        }
+        return false;
+    }
+}
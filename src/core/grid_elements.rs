@@ -1,7 +1,11 @@
 pub mod ant;
-pub mod dirt;
+pub mod aphid;
+pub mod caste;
+pub mod corpse;
 pub mod empty;
 pub mod food;
+pub mod garden;
 pub mod grid_element;
 pub mod hive;
+pub mod predator;
 pub mod state;
@@ -4,18 +4,33 @@ use colored::{Color, Colorize};
 use image::Rgb;
 use rand::distributions::WeightedIndex;
 use rand::prelude::Distribution;
-use strum::IntoEnumIterator;
+use rand::Rng;
 
 use crate::core::grid_elements::state::State;
 use crate::core::grid_elements::state::State::{Battle, Carrying, Food};
 use crate::core::{
     ant_grid::AntGrid,
     signals::{Signal, SignalType},
-    {Coord, Dir},
+    Coord,
 };
-use crate::core::{team_element::ElementType, Team};
+use crate::core::{team_element::ElementType, Infection, Options, Season, Team, Terrain};
 
+use super::caste::Caste;
+use super::corpse::Corpse;
+use super::food::FoodKind;
 use super::grid_element::GridElement;
+use super::hive::Hive;
+
+/// Rounds an ant may spend carrying a dead nestmate before putting it down.
+const UNDERTAKING_ROUNDS: usize = 200;
+/// Rounds between the health rolls of a sick ant.
+const SICKNESS_STAGE_ROUNDS: usize = 50;
+/// Rounds of sickness a single grooming takes off.
+const GROOM_ROUNDS: usize = 20;
+/// Fury gained for each point of damage taken.
+const FURY_PER_HIT: usize = 10;
+/// Fury needed for each extra point of damage dealt.
+const FURY_PER_DAMAGE: usize = 10;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Ant {
@@ -26,6 +41,18 @@ pub(crate) struct Ant {
     signals: VecDeque<Signal>,
     init_propagate: usize,
     rage: usize,
+    /// Anger built up by being attacked, fading by one each round.
+    fury: usize,
+    caste: Caste,
+    cooldown: usize,
+    load: usize,
+    age: usize,
+    hunger: usize,
+    /// Rounds spent standing in water.
+    wet: usize,
+    infection: Infection,
+    /// Set while carrying a load handed over by a nestmate.
+    relayed: bool,
 }
 
 impl GridElement for Ant {
@@ -37,16 +64,69 @@ impl GridElement for Ant {
     }
 
     fn decide(&mut self, grid: &mut AntGrid) -> Coord {
+        let options = grid.options_for(&self.team);
+        self.age += 1;
+        self.hunger += 1;
+        self.fury = self.fury.saturating_sub(1);
+        if self.age > options.lifespan || (options.hunger > 0 && self.hunger > options.hunger) {
+            self.health = 0;
+            return self.pos;
+        }
+        if grid.water(&self.pos) > 0 {
+            self.wet += 1;
+            if self.wet > options.drown_rounds {
+                self.health = 0;
+                return self.pos;
+            }
+        } else {
+            self.wet = 0;
+        }
+        self.progress_disease(grid, &options);
+        if self.health == 0 {
+            return self.pos;
+        }
+        if self.cooldown > 0 {
+            self.cooldown -= 1;
+            return self.pos;
+        }
+        self.cooldown = self.caste.move_interval() - 1;
+        let clock = grid.clock();
+        // Idle ants that sit out the round head back to shelter and wait there.
+        let resting = matches!(self.state, Food { .. })
+            && grid.rng.gen_range(0..100) >= clock.activity(&options);
+        if resting {
+            if clock.season() == Some(Season::Winter) {
+                self.hunger -= 1;
+            }
+            self.signals.clear();
+            if self.in_nest(grid) {
+                return self.pos;
+            }
+        }
         self.init();
-        self.init_propagate = grid.options.propagation;
-        self.rage = grid.options.rage;
+        self.init_propagate = options.propagation;
+        self.rage = options.rage;
         if let State::Dirt { prev_state: _ } = &self.get_state() {
         } else {
-            if !grid.hive_exists(self.team) {
-                self.state = Battle { rage: self.rage };
+            if !grid.hive_exists(&self.team) {
+                self.state = Battle {
+                    rage: self.rage.saturating_add(self.fury),
+                };
+            } else if matches!(self.state, Battle { .. })
+                && self.health * 100 < self.max_health() * options.retreat
+                && self.fury < options.rage
+            {
+                self.state = State::Retreating;
+            } else if let Food { pheromones: _ } = self.state {
+                if self.is_hungry(grid) {
+                    self.state = State::Hungry;
+                }
             }
         }
         let res = match &self.state {
+            _ if resting => self
+                .pick_best_pheromones(grid, true)
+                .unwrap_or(self.random_dir(grid)),
             State::Dirt { prev_state } => {
                 self.state = *prev_state.clone();
                 self.pos
@@ -74,35 +154,115 @@ impl GridElement for Ant {
                 };
                 self.find_best(grid)
             }
+            State::Founding { target, rounds } => {
+                if *rounds == 0 || grid.route_distance(&self.pos, target) < 1.5 {
+                    grid.put(Hive::new(self.pos, self.team.clone(), self.load, &options));
+                    self.health = 0;
+                    self.pos
+                } else {
+                    self.state = State::Founding {
+                        target: *target,
+                        rounds: rounds - 1,
+                    };
+                    self.find_best(grid)
+                }
+            }
+            State::Undertaking {
+                prev_state,
+                target,
+                rounds,
+                infected,
+            } => {
+                if *rounds == 0 || grid.route_distance(&self.pos, target) < 1.5 {
+                    let infected = *infected;
+                    self.state = *prev_state.clone();
+                    self.drop_corpse(grid, infected);
+                    self.pos
+                } else {
+                    self.state = State::Undertaking {
+                        prev_state: prev_state.clone(),
+                        target: *target,
+                        rounds: rounds - 1,
+                        infected: *infected,
+                    };
+                    self.find_best(grid)
+                }
+            }
+            State::Hauling {
+                prev_state,
+                terrain,
+                target,
+                rounds,
+            } => {
+                if *rounds == 0
+                    || grid.route_distance(&self.pos, target) < 1.5
+                    || grid.near_water(&self.pos)
+                {
+                    let (target, terrain) = (*target, *terrain);
+                    self.state = *prev_state.clone();
+                    self.drop_dirt(grid, terrain);
+                    if grid.is_hive_same_team(&target, &self.team) && self.is_hungry(grid) {
+                        self.eat_at_hive(grid, target);
+                    }
+                    self.pos
+                } else {
+                    self.state = State::Hauling {
+                        prev_state: prev_state.clone(),
+                        terrain: *terrain,
+                        target: *target,
+                        rounds: rounds - 1,
+                    };
+                    self.find_best(grid)
+                }
+            }
             _ => self.find_best(grid),
         };
-        self.pos = res;
-        if grid.is_dirt(&self.pos) {
-            match &self.state {
-                State::Dirt { prev_state: _ } => (),
-                _ => {
-                    self.state = State::Dirt {
+        let terrain = grid.terrain(&res);
+        let solid = grid.is_dirt(&res);
+        let hauling = matches!(self.state, State::Hauling { .. });
+        if res != self.pos && !(solid && hauling) && (!solid || grid.remove_dirt(&res)) {
+            self.pos = res;
+            if solid && options.haul_rounds > 0 && self.hauls() {
+                let dump = grid.dump_site(&self.pos, &self.team);
+                if let (Some(terrain), Some(target)) = (terrain, dump) {
+                    self.state = State::Hauling {
                         prev_state: Box::new(self.state.clone()),
+                        terrain,
+                        target,
+                        rounds: options.haul_rounds,
                     };
-                    grid.remove_dirt(&self.pos);
                 }
             }
+            if let Some(terrain) = terrain.filter(|t| t.traversal_cost() > 0) {
+                self.state = State::Dirt {
+                    prev_state: Box::new(self.state.clone()),
+                };
+                self.cooldown += terrain.traversal_cost() - 1;
+            }
         }
         self.cleanup(grid);
-        res
+        self.pos
     }
     fn team(&self) -> Option<Team> {
-        Some(self.team)
+        Some(self.team.clone())
     }
     fn type_elem(&self) -> ElementType {
         ElementType::Ant
     }
     fn attacked(&mut self, damage: usize) {
         self.health = self.health.checked_sub(damage).unwrap_or(0);
+        self.fury = self
+            .fury
+            .saturating_add(damage.saturating_mul(FURY_PER_HIT));
         if let Food { pheromones: _ } = self.get_state() {
-            self.state = State::Battle { rage: self.rage };
+            self.state = State::Battle {
+                rage: self.rage.saturating_add(self.fury),
+            };
         }
     }
+    fn armour(&self) -> usize {
+        return self.caste.armour();
+    }
     fn color(&self) -> Rgb<u8> {
         return self.team.color;
     }
@@ -112,12 +272,29 @@ impl GridElement for Ant {
     fn is_removed(&self) -> bool {
         return self.health == 0;
     }
-    fn pass_food(&mut self, other_pheromones: usize) -> Option<usize> {
+    fn infection(&self) -> Infection {
+        return self.infection;
+    }
+    fn infect(&mut self, rounds: usize) {
+        if self.infection == Infection::Susceptible {
+            self.infection = Infection::Infected { rounds };
+        }
+    }
+    fn groom(&mut self, rounds: usize) {
+        if let Infection::Infected { rounds: left } = self.infection {
+            self.infection = Infection::Infected {
+                rounds: left.saturating_sub(rounds),
+            };
+        }
+    }
+    fn pass_food(&mut self, other_pheromones: usize, food: usize) -> Option<usize> {
         match self.state {
-            Food { pheromones } => {
+            Food { pheromones } if self.caste.capacity() > 0 => {
                 self.state = Carrying {
                     pheromones: other_pheromones + 1,
                 };
+                self.load = food;
+                self.relayed = true;
                 Some(pheromones)
             }
             _ => None,
@@ -137,8 +314,16 @@ impl Display for Ant {
                 propagated: _,
             } => "t",
             State::Dirt { prev_state: _ } => "i",
+            State::Hungry => "e",
+            State::Retreating => "r",
+            State::Founding {
+                target: _,
+                rounds: _,
+            } => "q",
+            State::Hauling { .. } => "d",
+            State::Undertaking { .. } => "u",
         };
-        let color: Color = self.team.into();
+        let color = Color::from(&self.team);
         write!(f, "{}", state.color(color))
     }
 }
@@ -150,12 +335,16 @@ impl Ant {
         }
         match self.state {
             Carrying { pheromones: _ } => self
-                .pick_best_pheromones(grid)
+                .pick_best_pheromones(grid, true)
                 .unwrap_or(self.random_dir(grid)),
+            Food { pheromones: _ } if self.caste == Caste::Scout => self.random_dir(grid),
             Food { pheromones: _ } => self
-                .pick_best_pheromones(grid)
+                .pick_best_pheromones(grid, false)
                 .unwrap_or(self.random_dir(grid)),
-            Battle { rage: _ } => self.random_dir(grid),
+            State::Hungry | State::Retreating => self
+                .pick_best_pheromones(grid, true)
+                .unwrap_or(self.random_dir(grid)),
+            Battle { rage } => self.charge(grid, rage),
             _ => self.a_star_find(grid),
         }
     }
@@ -169,19 +358,76 @@ impl Ant {
             _ => self.state.clone(),
         }
     }
-    pub fn new(pos: &Coord, team: &Team) -> Self {
+    pub fn new(pos: &Coord, team: &Team, caste: Caste) -> Self {
         return Ant {
             pos: pos.clone(),
             state: Food { pheromones: 0 },
             team: team.clone(),
-            health: team.health,
+            health: team.health * caste.health(),
             signals: VecDeque::new(),
             init_propagate: 0,
             rage: 0,
+            fury: 0,
+            caste,
+            cooldown: 0,
+            load: 0,
+            age: 0,
+            hunger: 0,
+            wet: 0,
+            infection: Infection::Susceptible,
+            relayed: false,
+        };
+    }
+    /// A founder carrying `food` to start a new hive near `target`.
+    pub fn founder(pos: &Coord, team: &Team, target: Coord, food: usize, rounds: usize) -> Self {
+        let mut ant = Ant::new(pos, team, Caste::Worker);
+        ant.state = State::Founding { target, rounds };
+        ant.load = food;
+        return ant;
+    }
+    /// A soldier guarding a founding party on its way to `target`.
+    pub fn escort(pos: &Coord, team: &Team, target: Coord) -> Self {
+        let mut ant = Ant::new(pos, team, Caste::Soldier);
+        ant.state = State::Targeted {
+            prev_state: Box::new(Food { pheromones: 0 }),
+            coord: target,
+            propagated: usize::MAX,
         };
+        return ant;
+    }
+    /// A soldier hatched in a hurry to fight off an attack on its hive.
+    pub fn defender(pos: &Coord, team: &Team, rage: usize) -> Self {
+        let mut ant = Ant::new(pos, team, Caste::Soldier);
+        ant.state = Battle { rage };
+        return ant;
+    }
+    /// Whether dirt the ant digs out is hauled away, only by diggers and idle ants so no
+    /// errand is dropped for it.
+    fn hauls(&self) -> bool {
+        return self.caste == Caste::Digger || matches!(self.state, Food { .. });
+    }
+    /// Whether the ant rests in shelter, underground off the shafts or beside one of its hives.
+    fn in_nest(&self, grid: &AntGrid) -> bool {
+        return (self.pos.layer > 0 && !grid.is_entrance(&self.pos))
+            || grid
+                .neighbours(&self.pos)
+                .iter()
+                .any(|pos| grid.is_hive_same_team(pos, &self.team));
+    }
+    fn max_health(&self) -> usize {
+        return self.team.health * self.caste.health();
+    }
+    fn heal_at_hive(&mut self, grid: &AntGrid) {
+        let heal = grid.options_for(&self.team).heal;
+        self.health = (self.health + heal).min(self.max_health());
     }
     fn init(&mut self) {
-        match self.signals.iter().max_by_key(|m| m.propagate) {
+        match self
+            .signals
+            .iter()
+            .filter(|s| self.caste.responds_to(s.signal_type))
+            .max_by_key(|m| m.propagate)
+        {
             None => return,
             Some(i) => {
                 let old_state = &self.state;
@@ -200,7 +446,9 @@ impl Ant {
                     Food { pheromones: _ } => {
                         i.signal_type == SignalType::Food || i.signal_type == SignalType::Battle
                     }
-                    Carrying { pheromones: _ } => i.signal_type == SignalType::Carry,
+                    Carrying { pheromones: _ } | State::Hungry => {
+                        i.signal_type == SignalType::Carry
+                    }
                     State::Battle { rage: _ } => i.signal_type == SignalType::Battle,
                     _ => false,
                 } {
@@ -231,35 +479,45 @@ impl Ant {
     fn run_action(&mut self, pos: Coord, grid: &mut AntGrid) -> bool {
         match &self.state {
             Carrying { pheromones } => {
-                if grid.is_hive_same_team(&pos, self.team) {
+                if grid.is_hive_same_team(&pos, &self.team) {
                     grid.send_signal(
                         &pos,
                         Signal {
                             coord: pos,
-                            signal_type: SignalType::Deliver,
+                            signal_type: SignalType::Deliver(self.load),
                             propagate: 0,
                         },
                         self.team_element(),
                     );
                     self.send_carry(grid, pos);
+                    grid.record_delivery(&self.team, self.relayed);
+                    self.load = 0;
+                    self.relayed = false;
+                    if self.is_hungry(grid) {
+                        self.eat_at_hive(grid, pos);
+                    }
+                    self.heal_at_hive(grid);
                     self.state = Food { pheromones: 0 };
                     return true;
                 }
-                // if grid.is_same_team_elem(&pos, &self.team_element()) {
-                //     if grid.get_pheromones(&pos, self.team, !self.state.get_bool())
-                //         < grid.get_pheromones(&self.pos, self.team, !self.state.get_bool())
-                //     {
-                //         match grid.pass_food(&pos, *pheromones) {
-                //             None => (),
-                //             Some(pheromones) => {
-                //                 self.state = Food {
-                //                     pheromones: pheromones + 1,
-                //                 };
-                //                 return true;
-                //             }
-                //         }
-                //     }
-                // }
+                if grid.options_for(&self.team).relay
+                    && grid.is_same_team_elem(&pos, &self.team_element())
+                    && grid.get_pheromones(&pos, &self.team, !self.state.get_bool())
+                        < grid.get_pheromones(&self.pos, &self.team, !self.state.get_bool())
+                {
+                    match grid.pass_food(&pos, *pheromones, self.load) {
+                        None => (),
+                        Some(pheromones) => {
+                            grid.record_handoff(&self.team);
+                            self.state = Food {
+                                pheromones: pheromones + 1,
+                            };
+                            self.load = 0;
+                            self.relayed = false;
+                            return true;
+                        }
+                    }
+                }
                 return false;
             }
             Food {
@@ -268,8 +526,38 @@ impl Ant {
                 if self.should_battle(grid, pos, false) {
                     return true;
                 }
-                if grid.is_food(&pos) {
-                    grid.eat_food(&pos);
+                if self.health < self.max_health() && grid.is_hive_same_team(&pos, &self.team) {
+                    self.heal_at_hive(grid);
+                    return true;
+                }
+                if grid.is_sick_nestmate(&pos, &self.team)
+                    && grid.rng.gen_range(0..100) < grid.options_for(&self.team).hygiene
+                {
+                    grid.groom(&pos, GROOM_ROUNDS);
+                    return true;
+                }
+                if self.caste.capacity() > 0 && grid.is_nestmate_corpse(&pos, &self.team) {
+                    let infected = grid.is_infectious(&pos);
+                    grid.take_food(&pos, usize::MAX);
+                    self.state = State::Undertaking {
+                        prev_state: Box::new(self.state.clone()),
+                        target: grid.waste_site(&self.pos, &self.team),
+                        rounds: UNDERTAKING_ROUNDS,
+                        infected,
+                    };
+                    return true;
+                }
+                if self.caste.capacity() > 0
+                    && (grid.is_enemy_garden(&pos, &self.team)
+                        || grid.has_honeydew(&pos)
+                        || grid.is_enemy_corpse(&pos, &self.team))
+                {
+                    self.collect(grid, pos);
+                    return true;
+                }
+                if self.caste.capacity() > 0 && grid.is_food(&pos) && self.wants_food(grid, &pos) {
+                    let carry = grid.options_for(&self.team).carry;
+                    self.load = grid.eat_food(&pos, self.caste.capacity() * carry);
                     self.send_food_signal(grid, pos);
                     self.state = Carrying { pheromones: 0 };
                     return true;
@@ -277,6 +565,25 @@ impl Ant {
                 return false;
             }
             State::Battle { rage: _ } => self.should_battle(grid, pos, true),
+            State::Hungry => {
+                if grid.is_hive_same_team(&pos, &self.team) {
+                    self.eat_at_hive(grid, pos);
+                    self.heal_at_hive(grid);
+                    self.state = Food { pheromones: 0 };
+                    return true;
+                }
+                return false;
+            }
+            State::Retreating => {
+                if grid.is_hive_same_team(&pos, &self.team) {
+                    self.heal_at_hive(grid);
+                    if self.health >= self.max_health() || grid.options_for(&self.team).heal == 0 {
+                        self.state = Food { pheromones: 0 };
+                    }
+                    return true;
+                }
+                return false;
+            }
             State::Targeted {
                 prev_state,
                 coord,
@@ -291,14 +598,14 @@ impl Ant {
             _ => false,
         }
     }
-    fn pick_best_pheromones(&mut self, grid: &mut AntGrid) -> Option<Coord> {
-        let cur = grid.get_pheromones(&self.pos, self.team, !self.state.get_bool());
+    fn pick_best_pheromones(&mut self, grid: &mut AntGrid, trail: bool) -> Option<Coord> {
+        let cur = grid.get_pheromones(&self.pos, &self.team, trail);
 
         let cells = self.get_nearby(grid);
         return cells
             .iter()
             .map(|pos| {
-                let f = grid.get_pheromones(&pos, self.team, !self.state.get_bool());
+                let f = grid.get_pheromones(&pos, &self.team, trail);
                 (pos.clone(), f)
             })
             .filter(|(_, p)| *p < cur)
@@ -307,26 +614,43 @@ impl Ant {
     }
 
     fn get_nearby(&self, grid: &mut AntGrid) -> Vec<Coord> {
-        return Dir::iter()
-            .filter_map(|d| self.pos.next_cell(&d))
+        return grid
+            .neighbours(&self.pos)
+            .into_iter()
             .filter(|p| !grid.is_blocked(p))
             .collect();
     }
     fn a_star_find(&self, grid: &AntGrid) -> Coord {
-        return Dir::iter()
-            .filter_map(|dir| self.pos.next_cell(&dir))
+        let hauling = matches!(self.state, State::Hauling { .. });
+        return grid
+            .neighbours(&self.pos)
+            .into_iter()
             .filter(|pos| !grid.is_blocked(pos))
-            .filter_map(|pos| self.get_dist(&pos).map(|dist| (pos, dist)))
+            .filter(|pos| !hauling || !grid.is_dirt(pos))
+            .filter_map(|pos| {
+                let effort = grid.terrain(&pos).and_then(|t| t.effort()).unwrap_or(0);
+                let water = grid.water(&pos) as f64;
+                self.get_dist(grid, &pos)
+                    .map(|dist| (pos, dist + effort as f64 / 2.0 + water))
+            })
             .min_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
             .map(|(pos, _)| pos)
             .unwrap_or(self.pos);
     }
+    /// Closes in on the nearest enemy within `rage` cells, so angrier ants chase further.
+    fn charge(&self, grid: &mut AntGrid, rage: usize) -> Coord {
+        let enemy = match grid.nearest_enemy(&self.pos, &self.team, rage as f64) {
+            Some(enemy) => enemy,
+            None => return self.random_dir(grid),
+        };
+        return self
+            .get_nearby(grid)
+            .into_iter()
+            .min_by(|a, b| a.distance(&enemy).partial_cmp(&b.distance(&enemy)).unwrap())
+            .unwrap_or(self.pos);
+    }
     fn search_action(&mut self, grid: &mut AntGrid) -> Option<Coord> {
-        for dir in Dir::iter() {
-            let pos = match self.pos.next_cell(&dir) {
-                None => continue,
-                Some(i) => i,
-            };
+        for pos in grid.neighbours(&self.pos) {
             if self.run_action(pos, grid) {
                 return Some(self.pos);
             }
@@ -356,9 +680,12 @@ impl Ant {
                 },
                 self.team_element(),
             );
-            self.state = State::Battle { rage: self.rage };
-            if attack {
-                grid.attack(&coord, &self.team);
+            self.state = State::Battle {
+                rage: self.rage.saturating_add(self.fury),
+            };
+            if attack && !self.raid(grid, coord) {
+                let damage = self.caste.damage() + self.fury / FURY_PER_DAMAGE;
+                grid.attack(&self.pos, &coord, &self.team, damage);
             }
             return true;
         }
@@ -375,13 +702,16 @@ impl Ant {
             self.team_element(),
         );
     }
-    fn get_dist(&self, pos: &Coord) -> Option<f64> {
+    fn get_dist(&self, grid: &AntGrid, pos: &Coord) -> Option<f64> {
         let res = match &self.state {
             State::Targeted {
                 prev_state: _,
                 coord,
                 propagated: _,
-            } => coord.distance(pos),
+            } => grid.route_distance(pos, coord),
+            State::Founding { target, rounds: _ } => grid.route_distance(pos, target),
+            State::Hauling { target, .. } => grid.route_distance(pos, target),
+            State::Undertaking { target, .. } => grid.route_distance(pos, target),
             _ => return None,
         };
         return Some(res);
@@ -391,15 +721,156 @@ impl Ant {
         if options.len() == 0 {
             return self.pos;
         }
-        let index = WeightedIndex::new(options.iter().map(|pos| {
-            if grid.is_dirt(pos) {
-                1
-            } else {
-                grid.options.dirt_penalty
-            }
-        }))
-        .unwrap();
+        const SCENT_GAIN: f64 = 10.0;
+        let team_options = grid.options_for(&self.team);
+        let foraging = matches!(self.state, Food { .. }) && self.caste.capacity() > 0;
+        let isolating = self.infection.is_infected() && team_options.hygiene > 0;
+        let weights: Vec<usize> = options
+            .iter()
+            .map(|pos| {
+                let weight = self.step_weight(grid, pos, team_options.dirt_penalty)
+                    / (1 + grid.water(pos) as usize);
+                let weight = match isolating {
+                    true => weight * 100 / (100 + team_options.hygiene * self.nestmates(grid, pos)),
+                    false => weight,
+                };
+                let weight = weight.max(1);
+                if !foraging {
+                    return weight;
+                }
+                let bias = 1.0 + team_options.smell * SCENT_GAIN * grid.scent(pos);
+                (weight as f64 * bias) as usize
+            })
+            .collect();
+        let index = WeightedIndex::new(weights).unwrap();
 
         return options[index.sample(&mut grid.rng)];
     }
+    /// Open cells are `dirt_penalty` times likelier than dirt, other terrain scales with effort.
+    fn step_weight(&self, grid: &mut AntGrid, pos: &Coord, dirt_penalty: usize) -> usize {
+        const SCALE: usize = 12;
+        let dirt = Terrain::Dirt.effort().unwrap();
+        let effort = grid.terrain(pos).and_then(|t| t.effort());
+        let weight = match (self.caste, effort) {
+            (Caste::Digger, Some(effort)) => dirt_penalty * SCALE / effort,
+            (Caste::Digger, None) => SCALE / dirt,
+            (_, Some(effort)) => SCALE / effort,
+            (_, None) => dirt_penalty * SCALE / dirt,
+        };
+        if self.caste == Caste::Scout && !self.explored(grid, pos) {
+            return weight * dirt_penalty;
+        }
+        return weight;
+    }
+    /// Drops the load on adjacent water to seal it off, otherwise on the adjacent cell with the
+    /// fewest open neighbours so tunnels stay open where possible. The load is packed away if
+    /// there is nowhere to put it.
+    fn drop_dirt(&mut self, grid: &mut AntGrid, terrain: Terrain) {
+        let spot = grid
+            .neighbours(&self.pos)
+            .into_iter()
+            .filter(|c| grid.can_deposit(c))
+            .min_by_key(|c| {
+                let open = grid
+                    .neighbours(c)
+                    .into_iter()
+                    .filter(|n| *n != self.pos && grid.can_deposit(n))
+                    .count();
+                (grid.water(c) == 0, open)
+            });
+        if let Some(spot) = spot {
+            grid.put_terrain(&spot, terrain);
+        }
+    }
+    /// Lays the carried nestmate on a free cell beside the ant, it is lost if there is none.
+    fn drop_corpse(&mut self, grid: &mut AntGrid, infected: bool) {
+        let spot = grid
+            .neighbours(&self.pos)
+            .into_iter()
+            .find(|c| !grid.is_blocked(c) && !grid.is_dirt(c) && !grid.is_entrance(c));
+        if let Some(spot) = spot {
+            grid.put(Corpse::new(&spot, &self.team, true, infected));
+        }
+    }
+    /// Catches the disease from sick or dead ants and contaminated ground nearby, or runs the
+    /// course of an infection already caught.
+    fn progress_disease(&mut self, grid: &mut AntGrid, options: &Options) {
+        match self.infection {
+            Infection::Susceptible => {
+                let contacts = grid
+                    .neighbours(&self.pos)
+                    .iter()
+                    .filter(|c| grid.is_infectious(c))
+                    .count()
+                    + grid.is_contaminated(&self.pos) as usize;
+                if (0..contacts).any(|_| grid.rng.gen_range(0..100) < options.infection_rate) {
+                    self.infect(options.sickness_rounds);
+                }
+                return;
+            }
+            Infection::Infected { rounds } => {
+                grid.contaminate(&self.pos);
+                if rounds % SICKNESS_STAGE_ROUNDS == 0
+                    && grid.rng.gen_range(0..100) < options.virulence
+                {
+                    self.health -= 1;
+                }
+            }
+            Infection::Recovered { .. } => (),
+        }
+        self.infection = self.infection.tick(options.immunity_rounds);
+    }
+    /// Living nestmates next to `pos`.
+    fn nestmates(&self, grid: &AntGrid, pos: &Coord) -> usize {
+        return grid
+            .neighbours(pos)
+            .iter()
+            .filter(|c| **c != self.pos && grid.ant_team(c).as_ref() == Some(&self.team))
+            .count();
+    }
+    /// Rolls the team's raid chance against an enemy hive at `coord`, carrying off part of its
+    /// stores and calling nestmates to it on success.
+    fn raid(&mut self, grid: &mut AntGrid, coord: Coord) -> bool {
+        let options = grid.options_for(&self.team);
+        if self.caste.capacity() == 0
+            || !grid.is_enemy_stores(&coord, &self.team)
+            || grid.rng.gen_range(0..100) >= options.raid
+        {
+            return false;
+        }
+        self.load = grid.raid_hive(&coord, self.caste.capacity() * options.carry);
+        self.send_food_signal(grid, coord);
+        self.state = Carrying { pheromones: 0 };
+        return true;
+    }
+    /// Takes food from a source that outlasts the visit, such as an aphid or a raided garden.
+    fn collect(&mut self, grid: &mut AntGrid, pos: Coord) {
+        let carry = grid.options_for(&self.team).carry;
+        self.load = grid.take_food(&pos, self.caste.capacity() * carry);
+        self.send_food_signal(grid, pos);
+        self.state = Carrying { pheromones: 0 };
+    }
+    /// Rolls the team's preference for the kind of food at `pos`.
+    fn wants_food(&self, grid: &mut AntGrid, pos: &Coord) -> bool {
+        let options = grid.options_for(&self.team);
+        let preference = match grid.food_kind(pos) {
+            Some(FoodKind::Protein) => options.protein_preference,
+            Some(FoodKind::Sugar) => options.sugar_preference,
+            None => 0,
+        };
+        return grid.rng.gen_range(0..100) < preference;
+    }
+    fn is_hungry(&self, grid: &AntGrid) -> bool {
+        let hunger = grid.options_for(&self.team).hunger;
+        return hunger > 0 && self.hunger >= hunger / 2;
+    }
+    fn eat_at_hive(&mut self, grid: &mut AntGrid, hive: Coord) {
+        if grid.take_food(&hive, 1) > 0 {
+            self.hunger = 0;
+        }
+    }
+    fn explored(&self, grid: &mut AntGrid, pos: &Coord) -> bool {
+        return grid.get_pheromones(pos, &self.team, true) != usize::MAX
+            || grid.get_pheromones(pos, &self.team, false) != usize::MAX;
+    }
 }
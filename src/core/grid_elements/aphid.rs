@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+use image::Rgb;
+use rand::Rng;
+
+use crate::core::{
+    signals::{Signal, SignalType},
+    team_element::{ElementType, TeamElement},
+    AntGrid, Coord, Team,
+};
+
+use super::grid_element::GridElement;
+
+/// Rounds between an aphid's steps.
+const MOVE_INTERVAL: usize = 8;
+/// Honeydew an aphid holds before it stops producing.
+const MAX_HONEYDEW: usize = 5;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Aphid {
+    pos: Coord,
+    health: usize,
+    honeydew: usize,
+    /// Team whose ants tend the aphid and defend it, passing to another team's ants once they
+    /// tend it with none of the herders beside it.
+    herder: Option<Team>,
+    age: usize,
+}
+
+impl GridElement for Aphid {
+    fn pos(&self) -> &Coord {
+        return &self.pos;
+    }
+    fn exists(&self) -> bool {
+        return !self.is_removed();
+    }
+    fn decide(&mut self, grid: &mut AntGrid) -> Coord {
+        self.age += 1;
+        let options = grid.options;
+        if options.honeydew_rounds > 0 && self.age % options.honeydew_rounds == 0 {
+            self.honeydew = (self.honeydew + 1).min(MAX_HONEYDEW);
+        }
+        let cells = grid.neighbours(&self.pos);
+        let tending: Vec<Team> = cells.iter().filter_map(|c| grid.ant_team(c)).collect();
+        if !tending.iter().any(|t| Some(t) == self.herder.as_ref()) {
+            if let Some(team) = tending.first() {
+                self.herder = Some(team.clone());
+            }
+        }
+        for cell in cells.iter() {
+            let intruder = match grid.ant_team(cell) {
+                Some(team) => Some(team) != self.herder,
+                None => grid.is_predator(cell),
+            };
+            if intruder && self.herder.is_some() {
+                self.call_herders(grid, *cell);
+            }
+        }
+        if self.age % MOVE_INTERVAL != 0 {
+            return self.pos;
+        }
+        let open: Vec<Coord> = cells
+            .into_iter()
+            .filter(|c| c.layer == self.pos.layer && !grid.is_blocked(c) && !grid.is_dirt(c))
+            .collect();
+        if !open.is_empty() {
+            self.pos = open[grid.rng.gen_range(0..open.len())];
+        }
+        return self.pos;
+    }
+    fn type_elem(&self) -> ElementType {
+        ElementType::Aphid
+    }
+    fn attacked(&mut self, damage: usize) {
+        self.health = self.health.saturating_sub(damage);
+    }
+    fn color(&self) -> Rgb<u8> {
+        return match self.honeydew {
+            0 => Rgb([90, 140, 60]),
+            _ => Rgb([150, 230, 90]),
+        };
+    }
+    fn is_removed(&self) -> bool {
+        return self.health == 0;
+    }
+    fn food(&self) -> usize {
+        return self.honeydew;
+    }
+    fn take_food(&mut self, amount: usize) -> usize {
+        let taken = amount.min(self.honeydew);
+        self.honeydew -= taken;
+        return taken;
+    }
+}
+
+impl Aphid {
+    pub fn new(pos: &Coord) -> Self {
+        return Aphid {
+            pos: pos.clone(),
+            health: 3,
+            honeydew: 0,
+            herder: None,
+            age: 0,
+        };
+    }
+    /// Signals the herding team's ants to fight off the intruder at `enemy`.
+    fn call_herders(&self, grid: &mut AntGrid, enemy: Coord) {
+        let team = self.herder.clone().unwrap();
+        let propagate = grid.options_for(&team).propagation;
+        grid.send_signal(
+            &self.pos,
+            Signal {
+                coord: enemy,
+                signal_type: SignalType::Battle,
+                propagate,
+            },
+            TeamElement {
+                element: ElementType::Ant,
+                team: Some(team),
+            },
+        );
+    }
+}
+
+impl Display for Aphid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", "a".bright_green())
+    }
+}
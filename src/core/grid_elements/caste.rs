@@ -0,0 +1,58 @@
+use strum_macros::EnumIter;
+
+use crate::core::signals::SignalType;
+
+#[derive(Debug, EnumIter, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Caste {
+    Worker,
+    Soldier,
+    Digger,
+    Scout,
+}
+
+impl Caste {
+    /// Multiplier on the team's base health.
+    pub fn health(&self) -> usize {
+        match self {
+            Caste::Worker => 1,
+            Caste::Soldier => 3,
+            Caste::Digger => 2,
+            Caste::Scout => 1,
+        }
+    }
+    pub fn armour(&self) -> usize {
+        match self {
+            Caste::Soldier => 50,
+            Caste::Digger => 25,
+            Caste::Worker | Caste::Scout => 0,
+        }
+    }
+    pub fn damage(&self) -> usize {
+        match self {
+            Caste::Soldier => 2,
+            _ => 1,
+        }
+    }
+    /// Rounds between moves, 1 moves every round.
+    pub fn move_interval(&self) -> usize {
+        match self {
+            Caste::Soldier | Caste::Digger => 2,
+            Caste::Worker | Caste::Scout => 1,
+        }
+    }
+    /// Food taken per trip, castes with no capacity never forage.
+    pub fn capacity(&self) -> usize {
+        match self {
+            Caste::Worker => 2,
+            Caste::Soldier => 0,
+            Caste::Digger | Caste::Scout => 1,
+        }
+    }
+    pub fn responds_to(&self, signal_type: SignalType) -> bool {
+        match self {
+            Caste::Worker | Caste::Digger => true,
+            Caste::Soldier => signal_type == SignalType::Battle,
+            Caste::Scout => signal_type == SignalType::Carry,
+        }
+    }
+}
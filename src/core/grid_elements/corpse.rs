@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use colored::{Color, Colorize};
+use image::Rgb;
+
+use crate::core::{team_element::ElementType, AntGrid, Coord, Team};
+
+use super::grid_element::GridElement;
+
+/// Rounds a corpse lies before it has decayed away.
+const DECAY_ROUNDS: usize = 3000;
+
+/// A dead ant, food for enemies and scavengers until its own colony clears it away.
+#[derive(Debug, Clone)]
+pub(crate) struct Corpse {
+    pos: Coord,
+    team: Team,
+    food: usize,
+    /// Set once nestmates have carried it to a waste pile.
+    disposed: bool,
+    infected: bool,
+    age: usize,
+}
+
+impl GridElement for Corpse {
+    fn pos(&self) -> &Coord {
+        return &self.pos;
+    }
+    fn exists(&self) -> bool {
+        return true;
+    }
+    fn decide(&mut self, _grid: &mut AntGrid) -> Coord {
+        self.age += 1;
+        if self.age > DECAY_ROUNDS {
+            self.food = 0;
+        }
+        return self.pos;
+    }
+    fn team(&self) -> Option<Team> {
+        return Some(self.team.clone());
+    }
+    fn type_elem(&self) -> ElementType {
+        ElementType::Corpse
+    }
+    fn attacked(&mut self, damage: usize) {
+        self.food = self.food.saturating_sub(damage);
+    }
+    fn color(&self) -> Rgb<u8> {
+        let Rgb([r, g, b]) = self.team.color;
+        return Rgb([r / 4 + 60, g / 4 + 50, b / 4 + 40]);
+    }
+    fn is_removed(&self) -> bool {
+        return self.food == 0;
+    }
+    fn food(&self) -> usize {
+        return self.food;
+    }
+    fn take_food(&mut self, amount: usize) -> usize {
+        let taken = amount.min(self.food);
+        self.food -= taken;
+        return taken;
+    }
+    fn is_disposed(&self) -> bool {
+        return self.disposed;
+    }
+    fn is_infectious(&self) -> bool {
+        return self.infected;
+    }
+}
+
+impl Corpse {
+    pub fn new(pos: &Coord, team: &Team, disposed: bool, infected: bool) -> Self {
+        return Corpse {
+            pos: pos.clone(),
+            team: team.clone(),
+            food: 2,
+            disposed,
+            infected,
+            age: 0,
+        };
+    }
+}
+
+impl Display for Corpse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let color = Color::from(&self.team);
+        write!(f, "{}", "+".color(color))
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 use ::colored::Colorize;
 use image::Rgb;
+use strum_macros::EnumIter;
 
 use crate::core::{
     team_element::{ElementType, TeamElement},
@@ -10,12 +11,23 @@ use crate::core::{
 
 use super::grid_element::GridElement;
 
-const STARTING_FOOD: usize = 10;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub(crate) enum FoodKind {
+    Sugar,
+    Protein,
+}
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct Food {
     pub pos: Coord,
     food: usize,
+    /// Amount the source started with, regrowth stops there.
+    size: usize,
+    /// Hive food gained per unit carried home.
+    quality: usize,
+    kind: FoodKind,
+    /// Whether regrowth is on, an empty source then stays to refill.
+    regrows: bool,
 }
 
 impl GridElement for Food {
@@ -23,9 +35,14 @@ impl GridElement for Food {
         return &self.pos;
     }
     fn exists(&self) -> bool {
-        return true;
+        return !self.is_removed();
     }
-    fn decide(&mut self, _grid: &mut AntGrid) -> Coord {
+    fn decide(&mut self, grid: &mut AntGrid) -> Coord {
+        let regrow = grid.options.food_regrow;
+        self.regrows = regrow > 0;
+        if regrow > 0 && grid.round() % regrow == 0 && grid.clock().food_factor() > 0 {
+            self.food = (self.food + 1).min(self.size);
+        }
         self.pos
     }
     fn type_elem(&self) -> ElementType {
@@ -36,25 +53,45 @@ impl GridElement for Food {
         self.food = self.food.saturating_sub(damage);
     }
     fn color(&self) -> Rgb<u8> {
-        return Rgb::from([0, 255 * (self.food / STARTING_FOOD) as u8, 0]);
+        let shade = (55 + 200 * self.food / self.size) as u8;
+        return match self.kind {
+            FoodKind::Sugar => Rgb::from([0, shade, 0]),
+            FoodKind::Protein => Rgb::from([0, shade, shade]),
+        };
     }
     fn is_removed(&self) -> bool {
-        return self.food == 0;
+        return self.food == 0 && !self.regrows;
+    }
+    fn food(&self) -> usize {
+        return self.food;
+    }
+    fn food_kind(&self) -> Option<FoodKind> {
+        return Some(self.kind);
+    }
+    fn quality(&self) -> usize {
+        return self.quality;
     }
 }
 
 impl Food {
-    pub fn new(pos: &Coord) -> Self {
+    pub fn new(pos: &Coord, size: usize, quality: usize, kind: FoodKind) -> Self {
         return Food {
             pos: pos.clone(),
-            food: STARTING_FOOD,
+            food: size.max(1),
+            size: size.max(1),
+            quality,
+            kind,
+            regrows: false,
         };
     }
 }
 
 impl Display for Food {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", "f".green().bold())
+        match self.kind {
+            FoodKind::Sugar => write!(f, "{}", "f".green().bold()),
+            FoodKind::Protein => write!(f, "{}", "f".cyan().bold()),
+        }
     }
 }
 
@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use colored::{Color, Colorize};
+use image::Rgb;
+
+use crate::core::{
+    signals::{Signal, SignalType},
+    team_element::ElementType,
+    AntGrid, Coord, Team,
+};
+
+use super::grid_element::GridElement;
+
+/// A leafcutter fungus garden beside a hive, growing fungus from delivered material.
+#[derive(Debug, Clone)]
+pub(crate) struct Garden {
+    pos: Coord,
+    team: Team,
+    health: usize,
+    material: usize,
+    fungus: usize,
+    /// Rounds since an ant of the team last came by.
+    untended: usize,
+    age: usize,
+}
+
+impl GridElement for Garden {
+    fn pos(&self) -> &Coord {
+        return &self.pos;
+    }
+    fn exists(&self) -> bool {
+        return true;
+    }
+    fn decide(&mut self, grid: &mut AntGrid) -> Coord {
+        let options = grid.options_for(&self.team);
+        self.age += 1;
+        if grid
+            .neighbours(&self.pos)
+            .iter()
+            .any(|c| grid.ant_team(c).as_ref() == Some(&self.team))
+        {
+            self.untended = 0;
+        } else {
+            self.untended += 1;
+        }
+        if options.fungus_rounds > 0 && self.age % options.fungus_rounds == 0 && self.material > 0 {
+            self.material -= 1;
+            self.fungus += 1;
+        }
+        if options.rot_rounds > 0 && self.untended > options.rot_rounds {
+            self.material = self.material.saturating_sub(1);
+            self.fungus = self.fungus.saturating_sub(1);
+            if self.material == 0 && self.fungus == 0 {
+                self.health = 0;
+            }
+        }
+        return self.pos;
+    }
+    fn team(&self) -> Option<Team> {
+        return Some(self.team.clone());
+    }
+    fn type_elem(&self) -> ElementType {
+        ElementType::Garden
+    }
+    fn attacked(&mut self, damage: usize) {
+        self.health = self.health.saturating_sub(damage);
+    }
+    fn color(&self) -> Rgb<u8> {
+        let Rgb([r, _, b]) = self.team.color;
+        let green = 80 + 17 * self.fungus.min(10) as u8;
+        return Rgb([r / 3, green, b / 3]);
+    }
+    fn recv_signal(&mut self, signal: Signal) {
+        if let SignalType::Deliver(material) = signal.signal_type {
+            self.material += material;
+        }
+    }
+    fn is_removed(&self) -> bool {
+        return self.health == 0;
+    }
+    fn food(&self) -> usize {
+        return self.fungus;
+    }
+    fn take_food(&mut self, amount: usize) -> usize {
+        let taken = amount.min(self.fungus);
+        self.fungus -= taken;
+        return taken;
+    }
+}
+
+impl Garden {
+    pub fn new(pos: &Coord, team: &Team, material: usize) -> Self {
+        return Garden {
+            pos: pos.clone(),
+            team: team.clone(),
+            health: 5,
+            material,
+            fungus: 0,
+            untended: 0,
+            age: 0,
+        };
+    }
+}
+
+impl Display for Garden {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let color = Color::from(&self.team);
+        write!(f, "{}", "g".color(color))
+    }
+}
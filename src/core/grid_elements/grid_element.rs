@@ -7,9 +7,10 @@ use std::{
 use image::Rgb;
 
 use crate::core::{
+    grid_elements::food::FoodKind,
     signals::Signal,
     team_element::{ElementType, TeamElement},
-    AntGrid, Coord, Team,
+    AntGrid, Coord, Infection, Team,
 };
 
 pub(crate) trait GridElement: Debug + Display + Send {
@@ -27,14 +28,46 @@ pub(crate) trait GridElement: Debug + Display + Send {
         }
     }
     fn attacked(&mut self, _damage: usize) {}
+    /// Percentage chance of shrugging off each point of damage.
+    fn armour(&self) -> usize {
+        return 0;
+    }
     fn color(&self) -> Rgb<u8>;
     fn recv_signal(&mut self, _signal: Signal) {}
     fn is_removed(&self) -> bool {
         return false;
     }
-    fn pass_food(&mut self, _: usize) -> Option<usize> {
+    /// Takes over a load of `food` from a nestmate whose trail count is `pheromones`, returning
+    /// the receiver's own count when it accepts.
+    fn pass_food(&mut self, _pheromones: usize, _food: usize) -> Option<usize> {
+        return None;
+    }
+    fn food(&self) -> usize {
+        return 0;
+    }
+    fn take_food(&mut self, _amount: usize) -> usize {
+        return 0;
+    }
+    fn food_kind(&self) -> Option<FoodKind> {
         return None;
     }
+    fn quality(&self) -> usize {
+        return 0;
+    }
+    /// Whether a corpse has already been carried to a waste pile.
+    fn is_disposed(&self) -> bool {
+        return false;
+    }
+    fn infection(&self) -> Infection {
+        return Infection::Susceptible;
+    }
+    /// Whether the element passes the disease on to ants that touch it.
+    fn is_infectious(&self) -> bool {
+        return self.infection().is_infected();
+    }
+    fn infect(&mut self, _rounds: usize) {}
+    /// Cuts `rounds` off a running infection.
+    fn groom(&mut self, _rounds: usize) {}
 }
 
 impl<T: GridElement + 'static> IntoHelper for T {
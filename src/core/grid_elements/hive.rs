@@ -1,18 +1,42 @@
-use std::fmt::Display;
+use std::{collections::VecDeque, fmt::Display};
 
 use colored::{Color, Colorize};
 use image::Rgb;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
 use strum::IntoEnumIterator;
 
-use crate::core::{signals::SignalType, team_element::ElementType, Ant, AntGrid, Coord, Dir, Team};
+use crate::core::{
+    signals::{Signal, SignalType},
+    team_element::{ElementType, TeamElement},
+    Ant, AntGrid, Caste, Coord, Dir, Options, Team,
+};
+
+use super::garden::Garden;
 
 use super::grid_element::GridElement;
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+
+/// Rounds between two hatchings of defenders.
+const DEFENCE_ROUNDS: usize = 50;
+/// Rounds for a damaged hive to repair one hit point from its stores.
+const REPAIR_ROUNDS: usize = 20;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub(crate) struct Hive {
     pos: Coord,
     team: Team,
     health: usize,
+    max_health: usize,
+    /// Set when attacked since the last round.
+    alarm: bool,
+    /// Rounds left before defenders can hatch again.
+    defence_cooldown: usize,
     food: usize,
+    /// Caste and rounds left until hatching, oldest first.
+    eggs: VecDeque<(Caste, usize)>,
+    /// Delivered leaf material waiting to be planted in a garden.
+    material: usize,
+    leafcutter: bool,
 }
 impl GridElement for Hive {
     fn pos(&self) -> &Coord {
@@ -22,56 +46,217 @@ impl GridElement for Hive {
         return true;
     }
     fn decide(&mut self, grid: &mut AntGrid) -> Coord {
-        for dir in Dir::iter() {
-            let next = self.pos.next_cell(&dir);
-            if next.is_some() {
-                let n = next.unwrap();
-                if !grid.is_blocked(&n) {
-                    if self.food > 0 {
-                        grid.put(Ant::new(&n, &self.team));
-                        self.food -= 1;
+        let options = grid.options_for(&self.team);
+        self.leafcutter = options.leafcutter;
+        self.max_health = Hive::full_health(self.food, &options);
+        self.health = self.health.min(self.max_health);
+        self.defence_cooldown = self.defence_cooldown.saturating_sub(1);
+        if self.alarm {
+            self.alarm = false;
+            self.call_defenders(grid, &options);
+        } else if self.health < self.max_health
+            && self.food > 0
+            && grid.round() % REPAIR_ROUNDS == 0
+        {
+            self.health += 1;
+            self.food -= 1;
+        }
+        if self.material > 0 {
+            self.plant(grid);
+        }
+        self.harvest(grid);
+        if options.expansion_food > 0 && self.food >= options.expansion_food {
+            self.send_founders(grid, &options);
+        }
+        self.lay_egg(grid, &options);
+        for (_, rounds) in self.eggs.iter_mut() {
+            *rounds = rounds.saturating_sub(1);
+        }
+        if let Some((caste, 0)) = self.eggs.front().copied() {
+            for dir in Dir::iter() {
+                let next = self.pos.next_cell(&dir);
+                if next.is_some() {
+                    let n = next.unwrap();
+                    if !grid.is_blocked(&n) {
+                        grid.put(Ant::new(&n, &self.team, caste));
+                        self.eggs.pop_front();
+                        break;
                     }
-                    break;
                 }
             }
         }
+        if self.food < options.egg_cost
+            && self.eggs.is_empty()
+            && self.material == 0
+            && grid.population(&self.team) == 0
+        {
+            self.health = 0;
+        }
         self.pos
     }
     fn team(&self) -> Option<Team> {
-        return Some(self.team);
+        return Some(self.team.clone());
     }
     fn type_elem(&self) -> ElementType {
         ElementType::Hive
     }
     fn attacked(&mut self, damage: usize) {
         self.health = self.health.checked_sub(damage).unwrap_or(0);
+        self.alarm = true;
     }
+    /// The team colour, darkening as the hive loses health.
     fn color(&self) -> Rgb<u8> {
-        return self.team.color;
+        let health = match self.max_health {
+            0 => 1.0,
+            max => self.health.min(max) as f64 / max as f64,
+        };
+        let Rgb(color) = self.team.color;
+        return Rgb(color.map(|c| (c as f64 * (0.3 + 0.7 * health)) as u8));
     }
     fn recv_signal(&mut self, signal: crate::core::signals::Signal) {
         match signal.signal_type {
-            SignalType::Deliver => self.food += 1,
+            SignalType::Deliver(food) if self.leafcutter => self.material += food,
+            SignalType::Deliver(food) => self.food += food,
             _ => {}
         };
     }
     fn is_removed(&self) -> bool {
         return self.health == 0;
     }
+    fn food(&self) -> usize {
+        return self.food;
+    }
+    fn take_food(&mut self, amount: usize) -> usize {
+        let taken = amount.min(self.food);
+        self.food -= taken;
+        return taken;
+    }
 }
 impl Hive {
-    pub fn new(pos: Coord, team: Team, food: usize) -> Self {
+    pub fn new(pos: Coord, team: Team, food: usize, options: &Options) -> Self {
+        let health = Hive::full_health(food, options);
         Hive {
             pos,
+            health,
+            max_health: health,
+            alarm: false,
+            defence_cooldown: 0,
             team,
-            health: team.health,
             food,
+            eggs: VecDeque::new(),
+            material: 0,
+            leafcutter: false,
+        }
+    }
+    /// Hit points of an undamaged hive, raised by its stores.
+    fn full_health(food: usize, options: &Options) -> usize {
+        return options.hive_health + food.checked_div(options.hive_food_health).unwrap_or(0);
+    }
+    fn send_founders(&mut self, grid: &mut AntGrid, options: &Options) {
+        let free: Vec<Coord> = Dir::iter()
+            .filter_map(|d| self.pos.next_cell(&d))
+            .filter(|c| !grid.is_blocked(c))
+            .collect();
+        if free.is_empty() {
+            return;
+        }
+        let target = match grid.expansion_site(&self.team, &self.pos, options.expansion_distance) {
+            None => return,
+            Some(target) => target,
+        };
+        let rounds = options.expansion_distance * 4;
+        grid.put(Ant::founder(
+            &free[0],
+            &self.team,
+            target,
+            options.expansion_food,
+            rounds,
+        ));
+        for pos in free.iter().skip(1).take(options.party_size) {
+            grid.put(Ant::escort(pos, &self.team, target));
         }
+        self.food -= options.expansion_food;
+    }
+    /// Puts delivered material into a garden beside the hive, starting one if there is none.
+    /// The material is stored as food when there is no room for a garden.
+    fn plant(&mut self, grid: &mut AntGrid) {
+        let cells: Vec<Coord> = Dir::iter().filter_map(|d| self.pos.next_cell(&d)).collect();
+        if let Some(garden) = cells.iter().find(|c| grid.is_garden(c, &self.team)) {
+            grid.feed_garden(garden, self.material);
+        } else if let Some(free) = cells
+            .iter()
+            .find(|c| !grid.is_blocked(c) && !grid.is_entrance(c))
+        {
+            grid.put(Garden::new(free, &self.team, self.material));
+        } else {
+            self.food += self.material;
+        }
+        self.material = 0;
+    }
+    /// Gathers the fungus of the gardens beside the hive to feed the larvae.
+    fn harvest(&mut self, grid: &mut AntGrid) {
+        for cell in Dir::iter().filter_map(|d| self.pos.next_cell(&d)) {
+            if grid.is_garden(&cell, &self.team) {
+                self.food += grid.take_food(&cell, usize::MAX);
+            }
+        }
+    }
+    /// Calls the colony to the hive and hatches soldiers beside it if the stores allow.
+    fn call_defenders(&mut self, grid: &mut AntGrid, options: &Options) {
+        let defenders = TeamElement {
+            element: ElementType::Ant,
+            team: Some(self.team.clone()),
+        };
+        grid.send_signal(
+            &self.pos,
+            Signal {
+                coord: self.pos,
+                signal_type: SignalType::Battle,
+                propagate: options.propagation,
+            },
+            defenders,
+        );
+        if self.defence_cooldown > 0 {
+            return;
+        }
+        self.defence_cooldown = DEFENCE_ROUNDS;
+        let free: Vec<Coord> = Dir::iter()
+            .filter_map(|d| self.pos.next_cell(&d))
+            .filter(|c| !grid.is_blocked(c) && !grid.is_dirt(c))
+            .collect();
+        for pos in free.iter().take(options.defenders) {
+            if self.food < options.egg_cost {
+                break;
+            }
+            self.food -= options.egg_cost;
+            grid.put(Ant::defender(pos, &self.team, options.rage));
+        }
+    }
+    /// Lays an egg from the food left over once every adult of the team could have a meal.
+    fn lay_egg(&mut self, grid: &mut AntGrid, options: &Options) {
+        let reserve = match options.hunger {
+            0 => 0,
+            _ => grid.population(&self.team),
+        };
+        if self.food == 0 || self.food < options.egg_cost + reserve {
+            return;
+        }
+        self.food -= options.egg_cost;
+        let caste = self.pick_caste(grid);
+        self.eggs.push_back((caste, options.hatch_time));
+    }
+    fn pick_caste(&self, grid: &mut AntGrid) -> Caste {
+        let options = grid.options_for(&self.team);
+        let castes: Vec<Caste> = Caste::iter().collect();
+        return match WeightedIndex::new(castes.iter().map(|c| options.caste_ratio(*c))) {
+            Ok(index) => castes[index.sample(&mut grid.rng)],
+            Err(_) => Caste::Worker,
+        };
     }
 }
 impl Display for Hive {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let color: Color = self.team.into();
+        let color = Color::from(&self.team);
         write!(f, "{}", "h".color(color).bold())
     }
 }
@@ -0,0 +1,148 @@
+use std::fmt::Display;
+
+use colored::Colorize;
+use image::Rgb;
+use rand::Rng;
+use strum_macros::EnumIter;
+
+use crate::core::{team_element::ElementType, AntGrid, Coord};
+
+use super::grid_element::GridElement;
+
+/// How far a spider can smell a corpse.
+const SCENT_RANGE: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub(crate) enum PredatorKind {
+    /// Roams open cells looking for ants and aphids.
+    Spider,
+    /// Sits still and ambushes ants and aphids that walk past.
+    Antlion,
+}
+
+impl PredatorKind {
+    pub fn health(&self) -> usize {
+        match self {
+            PredatorKind::Spider => 20,
+            PredatorKind::Antlion => 30,
+        }
+    }
+    pub fn damage(&self) -> usize {
+        match self {
+            PredatorKind::Spider => 2,
+            PredatorKind::Antlion => 4,
+        }
+    }
+    /// Rounds between actions, 1 acts every round.
+    pub fn move_interval(&self) -> usize {
+        match self {
+            PredatorKind::Spider => 2,
+            PredatorKind::Antlion => 1,
+        }
+    }
+    /// Rounds before the predator leaves the map.
+    pub fn lifespan(&self) -> usize {
+        match self {
+            PredatorKind::Spider => 2000,
+            PredatorKind::Antlion => 5000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Predator {
+    pos: Coord,
+    kind: PredatorKind,
+    health: usize,
+    cooldown: usize,
+    age: usize,
+}
+
+impl GridElement for Predator {
+    fn pos(&self) -> &Coord {
+        return &self.pos;
+    }
+    fn exists(&self) -> bool {
+        return true;
+    }
+    fn decide(&mut self, grid: &mut AntGrid) -> Coord {
+        self.age += 1;
+        if self.age > self.kind.lifespan() {
+            self.health = 0;
+            return self.pos;
+        }
+        if self.cooldown > 0 {
+            self.cooldown -= 1;
+            return self.pos;
+        }
+        self.cooldown = self.kind.move_interval() - 1;
+        let cells = grid.neighbours(&self.pos);
+        let prey = cells.iter().find(|c| grid.is_ant(c));
+        if let Some(prey) = prey.or_else(|| cells.iter().find(|c| grid.is_aphid(c))) {
+            grid.hunt(prey, self.kind.damage());
+            return self.pos;
+        }
+        if let Some(corpse) = cells.iter().find(|c| grid.is_corpse(c)) {
+            grid.take_food(corpse, 1);
+            return self.pos;
+        }
+        if self.kind != PredatorKind::Spider {
+            return self.pos;
+        }
+        let open: Vec<Coord> = cells
+            .into_iter()
+            .filter(|c| !grid.is_blocked(c) && !grid.is_dirt(c))
+            .collect();
+        if open.is_empty() {
+            return self.pos;
+        }
+        self.pos = match grid.nearest_corpse(&self.pos, SCENT_RANGE) {
+            Some(corpse) => *open
+                .iter()
+                .min_by(|a, b| {
+                    a.distance(&corpse)
+                        .partial_cmp(&b.distance(&corpse))
+                        .unwrap()
+                })
+                .unwrap(),
+            None => open[grid.rng.gen_range(0..open.len())],
+        };
+        return self.pos;
+    }
+    fn type_elem(&self) -> ElementType {
+        ElementType::Predator
+    }
+    fn attacked(&mut self, damage: usize) {
+        self.health = self.health.saturating_sub(damage);
+    }
+    fn color(&self) -> Rgb<u8> {
+        return match self.kind {
+            PredatorKind::Spider => Rgb([220, 220, 220]),
+            PredatorKind::Antlion => Rgb([150, 100, 50]),
+        };
+    }
+    fn is_removed(&self) -> bool {
+        return self.health == 0;
+    }
+}
+
+impl Predator {
+    pub fn new(pos: &Coord, kind: PredatorKind) -> Self {
+        return Predator {
+            pos: pos.clone(),
+            kind,
+            health: kind.health(),
+            cooldown: 0,
+            age: 0,
+        };
+    }
+}
+
+impl Display for Predator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            PredatorKind::Spider => write!(f, "{}", "X".white().bold()),
+            PredatorKind::Antlion => write!(f, "{}", "V".yellow().bold()),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::Coord;
+use crate::core::{Coord, Terrain};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub(crate) enum State {
@@ -19,6 +19,27 @@ pub(crate) enum State {
     Dirt {
         prev_state: Box<State>,
     },
+    Hungry,
+    /// Falling back to the hive to heal.
+    Retreating,
+    Founding {
+        target: Coord,
+        rounds: usize,
+    },
+    Hauling {
+        prev_state: Box<State>,
+        terrain: Terrain,
+        target: Coord,
+        rounds: usize,
+    },
+    /// Carrying a dead nestmate to the waste pile.
+    Undertaking {
+        prev_state: Box<State>,
+        target: Coord,
+        rounds: usize,
+        /// Whether the corpse still carries the disease.
+        infected: bool,
+    },
 }
 
 impl State {
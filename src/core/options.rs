@@ -1,5 +1,20 @@
+use strum_macros::EnumIter;
+
+use super::Caste;
+
+/// Where random food drops land on the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub(crate) enum FoodPattern {
+    Uniform,
+    /// Clustered around one random spot per drop.
+    Patches,
+    /// Along a column that moves one step across the map per drop.
+    Front,
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct Options {
+    /// How strongly foraging ants follow food scent.
     pub smell: f64,
     pub starting_food: usize,
     pub signal_radius: f64,
@@ -7,7 +22,114 @@ pub(crate) struct Options {
     pub speed: usize,
     pub propagation: usize,
     pub decay: usize,
+    /// Rounds a fighting ant keeps up the fight, it chases enemies up to its remaining rage in
+    /// cells away.
     pub rage: usize,
+    /// Spawn weights of each caste, indexed by `Caste as usize`.
+    pub caste_ratio: [usize; 4],
+    pub lifespan: usize,
+    /// Rounds an ant survives without eating, ants head home at half of it. 0 turns off hunger.
+    pub hunger: usize,
+    pub egg_cost: usize,
+    pub hatch_time: usize,
+    /// Hive food needed to send a founding party, 0 disables expansion.
+    pub expansion_food: usize,
+    pub party_size: usize,
+    pub expansion_distance: usize,
+    /// Rounds an ant may spend hauling dug dirt before dropping it, 0 makes dug dirt vanish.
+    pub haul_rounds: usize,
+    /// Rounds between stability checks, open cells unsupported on two checks in a row cave in.
+    /// 0 keeps tunnels open forever.
+    pub collapse_rounds: usize,
+    /// Solid neighbours an open cell needs to stay up.
+    pub support: usize,
+    /// Layers of the world, the surface on top of the nest layers. Applied on reset.
+    pub layers: usize,
+    /// Rounds for an eaten food item to regain one unit, 0 disables regrowth. Items eaten to
+    /// nothing stay where they were and refill too.
+    pub food_regrow: usize,
+    /// Rounds between random food drops, 0 disables them.
+    pub food_drop_rounds: usize,
+    pub food_drop_amount: usize,
+    pub food_pattern: FoodPattern,
+    /// Rounds between large scheduled food bursts, 0 disables them.
+    pub food_burst_rounds: usize,
+    pub food_burst_amount: usize,
+    /// Units in each spawned food item.
+    pub food_amount: usize,
+    /// Hive food gained per unit of spawned food.
+    pub food_quality: usize,
+    /// Percentage of spawned food that is protein rather than sugar.
+    pub protein_ratio: usize,
+    /// Multiplies how much each caste carries per trip.
+    pub carry: usize,
+    /// Percentage chance an ant takes sugar it comes across.
+    pub sugar_preference: usize,
+    /// Percentage chance an ant takes protein it comes across.
+    pub protein_preference: usize,
+    /// Steps food scent spreads through open cells, 0 disables scent.
+    pub scent_radius: usize,
+    /// Rounds between rain showers, 0 disables rain.
+    pub rain_rounds: usize,
+    /// Surface cells each shower wets.
+    pub rain_amount: usize,
+    /// Rounds an ant survives standing in water.
+    pub drown_rounds: usize,
+    /// Rounds for every wet cell to lose a unit of water, 0 keeps water forever.
+    pub evaporation: usize,
+    /// Predators released on the surface at setup.
+    pub predators: usize,
+    /// Rounds between predators arriving, 0 disables arrivals.
+    pub predator_rounds: usize,
+    /// Rounds in a day, 0 keeps it always day.
+    pub day_length: usize,
+    /// Days in a year of four seasons, 0 disables seasons.
+    pub year_length: usize,
+    /// Percentage chance an idle ant goes out at night.
+    pub night_activity: usize,
+    /// Percentage chance an idle ant wakes from hibernation in winter.
+    pub winter_activity: usize,
+    /// Aphids placed on the surface at setup.
+    pub aphids: usize,
+    /// Rounds for an aphid to produce a unit of honeydew, 0 stops production.
+    pub honeydew_rounds: usize,
+    /// Leafcutter mode, deliveries go into fungus gardens beside the hive instead of its stores.
+    pub leafcutter: bool,
+    /// Rounds for a garden to turn a unit of material into fungus, 0 stops growth.
+    pub fungus_rounds: usize,
+    /// Rounds a garden lasts without an ant tending it before it starts to rot, 0 never rots.
+    pub rot_rounds: usize,
+    /// Rounds between outbreaks that infect one random ant, 0 keeps the colonies healthy.
+    pub outbreak_rounds: usize,
+    /// Percentage chance per round that each infectious contact passes the disease on.
+    pub infection_rate: usize,
+    /// Rounds an infected ant stays sick.
+    pub sickness_rounds: usize,
+    /// Rounds a recovered ant stays immune.
+    pub immunity_rounds: usize,
+    /// Percentage chance a sick ant loses health at each stage of the illness.
+    pub virulence: usize,
+    /// Percentage chance an ant grooms a sick nestmate it meets, also how strongly sick ants
+    /// keep away from nestmates.
+    pub hygiene: usize,
+    /// Percentage chance a fighting ant next to an enemy hive steals from its stores instead of
+    /// attacking it.
+    pub raid: usize,
+    /// Carrying ants hand their load to idle nestmates closer to the hive.
+    pub relay: bool,
+    /// Hit points of a hive with empty stores.
+    pub hive_health: usize,
+    /// Stored food per extra hit point of a hive, 0 ignores the stores.
+    pub hive_food_health: usize,
+    /// Soldiers a damaged hive hatches at once when it can pay for them.
+    pub defenders: usize,
+    /// Health an ant regains per visit to its hive.
+    pub heal: usize,
+    /// Percentage of extra damage for each nestmate also next to the target of an attack.
+    pub flanking: usize,
+    /// Percentage of full health under which a fighting ant falls back to its hive, unless
+    /// its fury has built past the team's rage.
+    pub retreat: usize,
 }
 
 impl Default for Options {
@@ -21,6 +143,66 @@ impl Default for Options {
             dirt_penalty: 5,
             rage: 10,
             decay: 1000,
+            caste_ratio: [6, 2, 1, 1],
+            lifespan: 5000,
+            hunger: 1000,
+            egg_cost: 1,
+            hatch_time: 20,
+            expansion_food: 100,
+            party_size: 2,
+            expansion_distance: 40,
+            haul_rounds: 0,
+            collapse_rounds: 0,
+            support: 3,
+            layers: 2,
+            food_regrow: 0,
+            food_drop_rounds: 0,
+            food_drop_amount: 10,
+            food_pattern: FoodPattern::Uniform,
+            food_burst_rounds: 0,
+            food_burst_amount: 200,
+            food_amount: 10,
+            food_quality: 1,
+            protein_ratio: 30,
+            carry: 1,
+            sugar_preference: 100,
+            protein_preference: 100,
+            scent_radius: 8,
+            rain_rounds: 0,
+            rain_amount: 100,
+            drown_rounds: 30,
+            evaporation: 50,
+            predators: 0,
+            predator_rounds: 0,
+            day_length: 0,
+            year_length: 0,
+            night_activity: 30,
+            winter_activity: 10,
+            aphids: 0,
+            honeydew_rounds: 50,
+            leafcutter: false,
+            fungus_rounds: 10,
+            rot_rounds: 500,
+            outbreak_rounds: 0,
+            infection_rate: 5,
+            sickness_rounds: 300,
+            immunity_rounds: 1000,
+            virulence: 20,
+            hygiene: 20,
+            raid: 50,
+            relay: true,
+            hive_health: 20,
+            hive_food_health: 10,
+            defenders: 2,
+            heal: 1,
+            flanking: 50,
+            retreat: 40,
         };
     }
 }
+
+impl Options {
+    pub fn caste_ratio(&self, caste: Caste) -> usize {
+        return self.caste_ratio[caste as usize];
+    }
+}
@@ -1,21 +1,25 @@
 use std::{cell::RefCell, rc::Rc, time::Instant};
 
-use image::Rgb;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 
 use crate::core::options::Options;
-use crate::core::{AntGrid, Coord, Team};
+use crate::core::{AntGrid, Coord, Relation, Team, TeamConfig, Terrain, Treaty};
 
 use super::{
     grid::Export,
     grid_elements::{grid_element::GridElement, hive::Hive},
-    Dirt, Food,
 };
 
 pub(crate) trait Runner {
     fn put<T: GridElement + 'static>(&mut self, elem: T);
+    fn put_terrain(&mut self, pos: Coord, terrain: Terrain);
+    fn put_midden(&mut self, pos: Coord);
+    fn put_water(&mut self, pos: Coord);
     fn set_opts(&mut self, options: Options);
+    fn set_team_opts(&mut self, team: usize, options: Option<Options>);
+    fn set_roster(&mut self, roster: Vec<TeamConfig>);
+    fn set_relation(&mut self, a: usize, b: usize, relation: Relation);
     fn reset(&mut self);
     fn export(&mut self) -> Export;
     fn run(&mut self) {}
@@ -24,6 +28,7 @@ pub(crate) trait Runner {
 pub(crate) struct BaseRunner {
     pub grid: AntGrid,
     pub(crate) teams: Vec<Team>,
+    roster: Vec<TeamConfig>,
     frames: usize,
 }
 
@@ -32,14 +37,44 @@ impl Runner for BaseRunner {
         self.put_raw(Rc::new(RefCell::new(elem)));
     }
 
+    fn put_terrain(&mut self, pos: Coord, terrain: Terrain) {
+        self.grid.put_terrain(&pos, terrain);
+    }
+
+    fn put_midden(&mut self, pos: Coord) {
+        self.grid.put_midden(&pos);
+    }
+
+    fn put_water(&mut self, pos: Coord) {
+        self.grid.put_water(&pos, 4);
+    }
+
     fn set_opts(&mut self, options: Options) {
         self.grid.options = options;
     }
 
+    fn set_team_opts(&mut self, team: usize, options: Option<Options>) {
+        match options {
+            Some(options) => self.grid.team_options.insert(team, options),
+            None => self.grid.team_options.remove(&team),
+        };
+    }
+
+    fn set_roster(&mut self, roster: Vec<TeamConfig>) {
+        self.roster = roster;
+    }
+
+    fn set_relation(&mut self, a: usize, b: usize, relation: Relation) {
+        self.grid.set_relation(a, b, relation);
+    }
+
     fn reset(&mut self) {
         self.frames = 0;
         let (rows, cols) = (self.grid.rows(), self.grid.cols());
-        self.grid = AntGrid::new(rows, cols);
+        let (options, team_options) = (self.grid.options, self.grid.team_options.clone());
+        self.grid = AntGrid::new(rows, cols, options.layers);
+        self.grid.options = options;
+        self.grid.team_options = team_options;
         self.teams.clear();
         self.default_setup();
     }
@@ -55,10 +90,11 @@ impl BaseRunner {
     pub fn put_raw(&mut self, elem: Rc<RefCell<dyn GridElement>>) {
         self.grid.put_raw(elem);
     }
-    pub fn new(rows: usize, cols: usize, options: Options) -> Self {
+    pub fn new(rows: usize, cols: usize, options: Options, roster: Vec<TeamConfig>) -> Self {
         let mut res = BaseRunner {
-            grid: AntGrid::new(rows, cols),
+            grid: AntGrid::new(rows, cols, options.layers),
             teams: Vec::new(),
+            roster,
             frames: 0,
         };
         res.set_opts(options);
@@ -66,36 +102,72 @@ impl BaseRunner {
         return res;
     }
     fn default_setup(&mut self) {
-        self.put_team(Rgb([255, 0, 0]), "Red");
-        self.put_team(Rgb([255, 0, 255]), "Purple");
-        self.put_team(Rgb([255, 255, 0]), "Yellow");
+        // Fill the nest first so hives dig their shafts out of the dirt.
+        for layer in self.grid.nest_layer()..self.grid.layers() {
+            for x in 0..self.grid.cols() {
+                for y in 0..self.grid.rows() {
+                    self.grid.put_terrain(&Coord { x, y, layer }, Terrain::Dirt);
+                }
+            }
+        }
+        for config in self.roster.clone() {
+            self.put_team(&config);
+        }
+        self.put_treaties();
         for _ in 0..((self.grid.rows() * self.grid.cols()) / 100) {
             let c = self.rand_coord();
-            self.grid.put(Food::new(&c));
-        }
-        for x in 0..self.grid.cols() {
-            for y in 0..self.grid.rows() {
-                self.grid.put(Dirt::new(&Coord { x, y }));
-            }
+            self.grid.put_food(&c);
         }
+        self.grid.spawn_predators(self.grid.options.predators);
+        self.grid.spawn_aphids(self.grid.options.aphids);
     }
     fn rand_coord(&mut self) -> Coord {
         let mut rng = SmallRng::from_entropy();
         let x = rng.gen_range(0..self.grid.cols());
         let y = rng.gen_range(0..self.grid.rows());
-        Coord { x, y }
+        Coord { x, y, layer: 0 }
     }
-    fn put_team(&mut self, color: Rgb<u8>, name: &'static str) {
+    fn put_team(&mut self, config: &TeamConfig) {
         let team = Team {
-            color,
+            color: config.color,
             id: self.teams.len(),
             health: 1,
-            name,
+            name: config.name.as_str().into(),
         };
-        self.teams.push(team);
-        let rand = self.rand_coord();
-        self.grid
-            .put(Hive::new(rand, team, self.grid.options.starting_food));
+        self.teams.push(team.clone());
+        let options = self.grid.options_for(&team);
+        let food = config.starting_food.unwrap_or(options.starting_food);
+        let layer = self.grid.nest_layer();
+        // Hives from the command line or a roster file may not fit the current grid.
+        let hives: Vec<Coord> = config
+            .hives
+            .iter()
+            .map(|pos| Coord { layer, ..*pos })
+            .filter(|pos| pos.x < self.grid.cols() && pos.y < self.grid.rows())
+            .collect();
+        if hives.is_empty() {
+            let rand = self.rand_coord();
+            self.grid
+                .put(Hive::new(Coord { layer, ..rand }, team, food, &options));
+        } else {
+            for pos in hives {
+                self.grid.put(Hive::new(pos, team.clone(), food, &options));
+            }
+        }
+    }
+    /// Schedules the relations the roster asks for, by team name.
+    fn put_treaties(&mut self) {
+        for (id, config) in self.roster.iter().enumerate() {
+            for (other, relation, round) in config.relations.iter() {
+                if let Some(other) = self.roster.iter().position(|c| &c.name == other) {
+                    self.grid.schedule_treaty(Treaty {
+                        teams: (id, other),
+                        relation: *relation,
+                        round: *round,
+                    });
+                }
+            }
+        }
     }
     pub(crate) fn run_dynamic(&mut self) {
         puffin::profile_function!();
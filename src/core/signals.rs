@@ -5,7 +5,7 @@ pub(crate) enum SignalType {
     Food,
     Carry,
     Battle,
-    Deliver,
+    Deliver(usize),
 }
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct Signal {
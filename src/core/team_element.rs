@@ -2,12 +2,15 @@ use super::Team;
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub(crate) enum ElementType {
     Ant,
-    Dirt,
     Empty,
     Food,
     Hive,
+    Predator,
+    Aphid,
+    Garden,
+    Corpse,
 }
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 pub(crate) struct TeamElement {
     pub element: ElementType,
     pub team: Option<Team>,
@@ -7,16 +7,24 @@ use std::{
 use crate::core::options::Options;
 use tracing::{event, Level};
 
-use super::{grid::Export, BaseRunner, GridElement, IntoHelper, Runner};
+use super::{
+    grid::Export, BaseRunner, Coord, GridElement, IntoHelper, Relation, Runner, TeamConfig, Terrain,
+};
 enum Command {
     RESET,
     STOP,
+    ROSTER(Vec<TeamConfig>),
+    RELATION(usize, usize, Relation),
 }
 pub(crate) struct ThreadRunner {
     handle: JoinHandle<()>,
     tx_opts: Sender<Options>,
+    tx_team_opts: Sender<(usize, Option<Options>)>,
     tx_command: Sender<Command>,
     tx_elem: Sender<Box<dyn IntoHelper>>,
+    tx_terrain: Sender<(Coord, Terrain)>,
+    tx_midden: Sender<Coord>,
+    tx_water: Sender<Coord>,
     rx_export: Receiver<Export>,
     last_expt: Export,
 }
@@ -24,8 +32,12 @@ struct RunnerHandle {
     runner: BaseRunner,
     tx_export: Sender<Export>,
     rx_opts: Receiver<Options>,
+    rx_team_opts: Receiver<(usize, Option<Options>)>,
     rx_command: Receiver<Command>,
     rx_elem: Receiver<Box<dyn IntoHelper>>,
+    rx_terrain: Receiver<(Coord, Terrain)>,
+    rx_midden: Receiver<Coord>,
+    rx_water: Receiver<Coord>,
 }
 impl RunnerHandle {
     fn run(&mut self) {
@@ -37,16 +49,34 @@ impl RunnerHandle {
                 Ok(command) => match command {
                     Command::STOP => break,
                     Command::RESET => self.runner.reset(),
+                    Command::ROSTER(roster) => self.runner.set_roster(roster),
+                    Command::RELATION(a, b, relation) => self.runner.set_relation(a, b, relation),
                 },
             }
             match self.rx_opts.recv_timeout(deadline) {
                 Err(_) => (),
                 Ok(opts) => self.runner.set_opts(opts),
             }
+            match self.rx_team_opts.recv_timeout(deadline) {
+                Err(_) => (),
+                Ok((team, opts)) => self.runner.set_team_opts(team, opts),
+            }
             match self.rx_elem.recv_timeout(deadline) {
                 Err(_) => (),
                 Ok(elem_box) => self.runner.put_raw(elem_box.into()),
             }
+            match self.rx_terrain.recv_timeout(deadline) {
+                Err(_) => (),
+                Ok((pos, terrain)) => self.runner.put_terrain(pos, terrain),
+            }
+            match self.rx_midden.recv_timeout(deadline) {
+                Err(_) => (),
+                Ok(pos) => self.runner.put_midden(pos),
+            }
+            match self.rx_water.recv_timeout(deadline) {
+                Err(_) => (),
+                Ok(pos) => self.runner.put_water(pos),
+            }
             self.runner.run_dynamic();
 
             if self.tx_export.send(self.runner.export()).is_err() {
@@ -61,9 +91,35 @@ impl Runner for ThreadRunner {
             .send(Box::new(elem))
             .expect("TODO: panic message");
     }
+    fn put_terrain(&mut self, pos: Coord, terrain: Terrain) {
+        self.tx_terrain
+            .send((pos, terrain))
+            .expect("TODO: panic message");
+    }
+    fn put_midden(&mut self, pos: Coord) {
+        self.tx_midden.send(pos).expect("TODO: panic message");
+    }
+    fn put_water(&mut self, pos: Coord) {
+        self.tx_water.send(pos).expect("TODO: panic message");
+    }
     fn set_opts(&mut self, options: Options) {
         self.tx_opts.send(options).expect("TODO: panic message");
     }
+    fn set_team_opts(&mut self, team: usize, options: Option<Options>) {
+        self.tx_team_opts
+            .send((team, options))
+            .expect("TODO: panic message");
+    }
+    fn set_roster(&mut self, roster: Vec<TeamConfig>) {
+        self.tx_command
+            .send(Command::ROSTER(roster))
+            .expect("TODO: panic message");
+    }
+    fn set_relation(&mut self, a: usize, b: usize, relation: Relation) {
+        self.tx_command
+            .send(Command::RELATION(a, b, relation))
+            .expect("TODO: panic message");
+    }
     fn reset(&mut self) {
         self.tx_command
             .send(Command::RESET)
@@ -78,18 +134,26 @@ impl Runner for ThreadRunner {
     }
 }
 impl ThreadRunner {
-    pub fn new(rows: usize, cols: usize, options: Options) -> Self {
+    pub fn new(rows: usize, cols: usize, options: Options, roster: Vec<TeamConfig>) -> Self {
         let (tx_opts, rx_opts) = mpsc::channel();
+        let (tx_team_opts, rx_team_opts) = mpsc::channel();
         let (tx_command, rx_command) = mpsc::channel();
         let (tx_elem, rx_elem) = mpsc::channel();
+        let (tx_terrain, rx_terrain) = mpsc::channel();
+        let (tx_midden, rx_midden) = mpsc::channel();
+        let (tx_water, rx_water) = mpsc::channel();
         let (tx_export, rx_export) = mpsc::channel();
         let handle = thread::spawn(move || {
             RunnerHandle {
-                runner: BaseRunner::new(rows, cols, options),
+                runner: BaseRunner::new(rows, cols, options, roster),
                 tx_export,
                 rx_opts,
+                rx_team_opts,
                 rx_command,
                 rx_elem,
+                rx_terrain,
+                rx_midden,
+                rx_water,
             }
             .run();
         });
@@ -97,9 +161,13 @@ impl ThreadRunner {
         return ThreadRunner {
             handle,
             tx_opts,
+            tx_team_opts,
             tx_command,
             rx_export,
             tx_elem,
+            tx_terrain,
+            tx_midden,
+            tx_water,
             last_expt: export,
         };
     }
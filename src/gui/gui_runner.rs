@@ -1,16 +1,27 @@
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 
 use eframe::Renderer;
-use egui::{Frame, Image, Pos2, TextureHandle, TextureOptions, Vec2};
+use egui::widgets::plot::{Legend, Line, Plot, PlotPoints};
+use egui::{Frame, Image, Pos2, TextureHandle, TextureOptions, Ui, Vec2};
+use image::Rgb;
 use puffin;
 use puffin_egui;
 
-use crate::core::Options;
-use crate::core::{BaseRunner, Coord, Dirt, Food, Hive, Runner, Team};
+use crate::core::{FoodPattern, Options};
+use strum::IntoEnumIterator;
+
+use crate::core::{
+    Aphid, BaseRunner, Caste, Coord, Food, FoodKind, Hive, Predator, PredatorKind, Relation,
+    Runner, Sir, Team, TeamConfig, Terrain,
+};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum SelectionMode {
-    DIRT,
+    DIRT(Terrain),
+    MIDDEN,
+    WATER,
+    PREDATOR(PredatorKind),
+    APHID,
     HIVE,
     FOOD,
 }
@@ -39,7 +50,7 @@ impl Timer {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 struct AddMode {
     team: Option<Team>,
     selection_mode: SelectionMode,
@@ -49,10 +60,6 @@ const FOOD_MODE: AddMode = AddMode {
     team: None,
     selection_mode: SelectionMode::FOOD,
 };
-const DIRT_MODE: AddMode = AddMode {
-    team: None,
-    selection_mode: SelectionMode::DIRT,
-};
 
 type RunnerMode = BaseRunner;
 
@@ -65,14 +72,25 @@ struct GUIrunner {
     add_mode: AddMode,
     profile: bool,
     options: Options,
+    team_options: HashMap<usize, Options>,
+    roster: Vec<TeamConfig>,
+    /// Layer shown, all of them side by side when `None`.
+    view: Option<usize>,
+    food_size: usize,
+    food_kind: FoodKind,
 }
 
 impl GUIrunner {
-    pub fn new(rows: usize, cols: usize, cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        roster: Vec<TeamConfig>,
+        cc: &eframe::CreationContext<'_>,
+    ) -> Self {
         let options = Options::default();
-        let mut runner = RunnerMode::new(rows, cols, options);
+        let mut runner = RunnerMode::new(rows, cols, options, roster.clone());
         let frames = runner.export().frames();
-        let image = runner.export().to_image();
+        let image = runner.export().to_image(None);
         let texture = cc
             .egui_ctx
             .load_texture("ants", image, TextureOptions::default());
@@ -85,29 +103,74 @@ impl GUIrunner {
             add_mode: FOOD_MODE,
             profile: false,
             options,
+            team_options: HashMap::new(),
+            roster,
+            view: None,
+            food_size: 10,
+            food_kind: FoodKind::Sugar,
         }
     }
     fn reset(&mut self) {
         self.runner.set_opts(self.options);
+        self.runner.set_roster(self.roster.clone());
+        for id in 0..self.roster.len() {
+            self.runner
+                .set_team_opts(id, self.team_options.get(&id).copied());
+        }
         self.runner.reset();
         self.timer_reset();
     }
+    fn add_team(&mut self) {
+        let name = format!("Team {}", self.roster.len() + 1);
+        self.roster
+            .push(TeamConfig::new(&name, Rgb(rand::random())));
+        self.reset();
+    }
+    fn remove_team(&mut self, idx: usize) {
+        self.roster.remove(idx);
+        self.team_options = self
+            .team_options
+            .drain()
+            .filter(|(id, _)| *id != idx)
+            .map(|(id, options)| (if id > idx { id - 1 } else { id }, options))
+            .collect();
+        self.add_mode = FOOD_MODE;
+        self.reset();
+    }
     fn timer_reset(&mut self) {
         self.timer = Timer::new(self.runner.export().frames());
     }
     fn add(&mut self, rect: Pos2, _drag: Vec2) {
         let y = rect.y as usize;
         let x = rect.x as usize;
-        let c = Coord { x, y };
+        let cols = self.runner.grid.cols();
+        let c = match self.view {
+            Some(layer) => Coord { x, y, layer },
+            None => Coord {
+                x: x % cols,
+                y,
+                layer: x / cols,
+            },
+        };
         match self.add_mode.selection_mode {
-            SelectionMode::HIVE => self.runner.put(Hive::new(
-                c,
-                self.add_mode.team.unwrap(),
-                self.options.starting_food,
+            SelectionMode::HIVE => {
+                let team = self.add_mode.team.clone().unwrap();
+                let options = *self.team_options.get(&team.id).unwrap_or(&self.options);
+                self.runner
+                    .put(Hive::new(c, team, options.starting_food, &options))
+            }
+            SelectionMode::FOOD => self.runner.put(Food::new(
+                &c,
+                self.food_size,
+                self.options.food_quality,
+                self.food_kind,
             )),
-            SelectionMode::FOOD => self.runner.put(Food::new(&c)),
-            SelectionMode::DIRT => {
-                self.runner.put(Dirt::new(&c));
+            SelectionMode::MIDDEN => self.runner.put_midden(c),
+            SelectionMode::WATER => self.runner.put_water(c),
+            SelectionMode::PREDATOR(kind) => self.runner.put(Predator::new(&c, kind)),
+            SelectionMode::APHID => self.runner.put(Aphid::new(&c)),
+            SelectionMode::DIRT(terrain) => {
+                self.runner.put_terrain(c, terrain);
                 // let mut bounds = [0, drag.x.round() as u32];
                 // bounds.sort();
                 // println!("{:?}", bounds);
@@ -134,6 +197,138 @@ impl GUIrunner {
     }
 }
 
+/// Settings the grid as a whole reads, only taken from the global options.
+fn world_options_ui(ui: &mut Ui, options: &mut Options) {
+    ui.add(
+        egui::Slider::new(&mut options.collapse_rounds, 0..=1000)
+            .text("Tunnel collapse rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.support, 0..=8).text("Tunnel support"));
+    ui.add(egui::Slider::new(&mut options.layers, 1..=4).text("Layers (applied on reset)"));
+    ui.add(
+        egui::Slider::new(&mut options.food_regrow, 0..=1000)
+            .text("Food regrowth rounds (0 = off)"),
+    );
+    ui.add(
+        egui::Slider::new(&mut options.food_drop_rounds, 0..=1000)
+            .text("Food drop rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.food_drop_amount, 1..=100).text("Food per drop"));
+    ui.add(
+        egui::Slider::new(&mut options.food_burst_rounds, 0..=20000)
+            .text("Food burst rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.food_burst_amount, 1..=2000).text("Food per burst"));
+    ui.add(egui::Slider::new(&mut options.food_amount, 1..=100).text("Spawned food size"));
+    ui.add(egui::Slider::new(&mut options.food_quality, 1..=10).text("Food nutrition"));
+    ui.add(egui::Slider::new(&mut options.protein_ratio, 0..=100).text("Protein share (%)"));
+    ui.add(
+        egui::Slider::new(&mut options.scent_radius, 0..=50).text("Food scent radius (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.rain_rounds, 0..=2000).text("Rain rounds (0 = off)"));
+    ui.add(egui::Slider::new(&mut options.rain_amount, 1..=1000).text("Raindrops per shower"));
+    ui.add(
+        egui::Slider::new(&mut options.evaporation, 0..=500).text("Evaporation rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.predators, 0..=50).text("Starting predators"));
+    ui.add(
+        egui::Slider::new(&mut options.predator_rounds, 0..=5000)
+            .text("Predator arrival rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.day_length, 0..=5000).text("Day length (0 = no nights)"));
+    ui.add(
+        egui::Slider::new(&mut options.year_length, 0..=100).text("Days per year (0 = no seasons)"),
+    );
+    ui.add(egui::Slider::new(&mut options.aphids, 0..=200).text("Starting aphids"));
+    ui.add(
+        egui::Slider::new(&mut options.honeydew_rounds, 0..=500).text("Honeydew rounds (0 = off)"),
+    );
+    ui.add(
+        egui::Slider::new(&mut options.outbreak_rounds, 0..=5000)
+            .text("Outbreak rounds (0 = no disease)"),
+    );
+    ui.horizontal(|ui| {
+        for pattern in FoodPattern::iter() {
+            ui.radio_value(&mut options.food_pattern, pattern, format!("{:?}", pattern));
+        }
+    });
+}
+
+/// Settings a team can override for its own ants, hives and gardens.
+fn team_options_ui(ui: &mut Ui, options: &mut Options) {
+    ui.add(egui::Slider::new(&mut options.smell, 0.01..=1.0).text("Smell sensitivity"));
+    ui.add(egui::Slider::new(&mut options.signal_radius, 0.0..=1000.0).text("Signal Radius"));
+    ui.add(egui::Slider::new(&mut options.decay, 0..=10000).text("Pheromone decay (rounds)"));
+    ui.add(egui::Slider::new(&mut options.starting_food, 1..=100).text("Starting Food"));
+    ui.add(egui::Slider::new(&mut options.propagation, 0..=10).text("propagation"));
+    ui.add(egui::Slider::new(&mut options.dirt_penalty, 1..=50).text("Dirt Penalty"));
+    ui.add(egui::Slider::new(&mut options.rage, 0..=100).text("Ant rage"));
+    ui.add(egui::Slider::new(&mut options.lifespan, 100..=20000).text("Ant lifespan"));
+    ui.add(egui::Slider::new(&mut options.hunger, 0..=5000).text("Rounds without food (0 = off)"));
+    ui.add(egui::Slider::new(&mut options.egg_cost, 1..=10).text("Egg cost"));
+    ui.add(egui::Slider::new(&mut options.hatch_time, 0..=200).text("Hatch time"));
+    ui.add(
+        egui::Slider::new(&mut options.expansion_food, 0..=1000).text("Expansion food (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.party_size, 0..=3).text("Founding escorts"));
+    ui.add(egui::Slider::new(&mut options.expansion_distance, 1..=500).text("Expansion distance"));
+    ui.add(egui::Slider::new(&mut options.haul_rounds, 0..=500).text("Dirt haul rounds (0 = off)"));
+    ui.add(egui::Slider::new(&mut options.drown_rounds, 1..=200).text("Rounds to drown"));
+    ui.add(egui::Slider::new(&mut options.night_activity, 0..=100).text("Night activity (%)"));
+    ui.add(egui::Slider::new(&mut options.winter_activity, 0..=100).text("Winter activity (%)"));
+    ui.checkbox(&mut options.leafcutter, "Leafcutter fungus gardens");
+    ui.add(egui::Slider::new(&mut options.fungus_rounds, 0..=200).text("Fungus growth rounds"));
+    ui.add(
+        egui::Slider::new(&mut options.rot_rounds, 0..=5000).text("Garden rot rounds (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.infection_rate, 0..=100).text("Infection rate (%)"));
+    ui.add(egui::Slider::new(&mut options.sickness_rounds, 0..=2000).text("Sickness rounds"));
+    ui.add(egui::Slider::new(&mut options.immunity_rounds, 0..=5000).text("Immunity rounds"));
+    ui.add(egui::Slider::new(&mut options.virulence, 0..=100).text("Virulence (%)"));
+    ui.add(egui::Slider::new(&mut options.hygiene, 0..=100).text("Hygiene (%)"));
+    ui.add(egui::Slider::new(&mut options.raid, 0..=100).text("Raid chance (%)"));
+    ui.checkbox(&mut options.relay, "Relay food between ants");
+    ui.add(egui::Slider::new(&mut options.hive_health, 1..=200).text("Hive health"));
+    ui.add(
+        egui::Slider::new(&mut options.hive_food_health, 0..=100)
+            .text("Food per extra hive health (0 = off)"),
+    );
+    ui.add(egui::Slider::new(&mut options.defenders, 0..=10).text("Defenders per alarm"));
+    ui.add(egui::Slider::new(&mut options.heal, 0..=5).text("Healing per hive visit"));
+    ui.add(egui::Slider::new(&mut options.flanking, 0..=200).text("Flanking bonus (%)"));
+    ui.add(egui::Slider::new(&mut options.retreat, 0..=100).text("Retreat below health (%)"));
+    ui.add(egui::Slider::new(&mut options.carry, 1..=10).text("Carry multiplier"));
+    ui.add(egui::Slider::new(&mut options.sugar_preference, 0..=100).text("Sugar preference (%)"));
+    ui.add(
+        egui::Slider::new(&mut options.protein_preference, 0..=100).text("Protein preference (%)"),
+    );
+    for caste in Caste::iter() {
+        ui.add(
+            egui::Slider::new(&mut options.caste_ratio[caste as usize], 0..=10)
+                .text(format!("{:?} ratio", caste)),
+        );
+    }
+}
+
+/// Susceptible, infected and recovered ants of `team` over time.
+fn sir_plot(ui: &mut Ui, team: &Team, history: &[Sir]) {
+    let series = |count: fn(&Sir) -> usize| -> PlotPoints {
+        return history
+            .iter()
+            .map(|s| [s.round as f64, count(s) as f64])
+            .collect();
+    };
+    ui.label(&*team.name);
+    Plot::new(format!("SIR {}", team.id))
+        .height(100.0)
+        .legend(Legend::default())
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(series(|s| s.susceptible)).name("Susceptible"));
+            plot_ui.line(Line::new(series(|s| s.infected)).name("Infected"));
+            plot_ui.line(Line::new(series(|s| s.recovered)).name("Recovered"));
+        });
+}
+
 impl eframe::App for GUIrunner {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.runner.run();
@@ -150,31 +345,154 @@ impl eframe::App for GUIrunner {
             {
                 self.timer_reset();
             }
-            ui.add(egui::Slider::new(&mut self.options.smell, 0.01..=1.0).text("Smell offset"));
-            ui.add(
-                egui::Slider::new(&mut self.options.signal_radius, 0.0..=1000.0)
-                    .text("Signal Radius"),
-            );
-            ui.add(
-                egui::Slider::new(&mut self.options.decay, 0..=10000)
-                    .text("Pheromone decay (rounds)"),
-            );
-            ui.add(
-                egui::Slider::new(&mut self.options.starting_food, 1..=100).text("Starting Food"),
-            );
-            ui.add(egui::Slider::new(&mut self.options.propagation, 0..=10).text("propagation"));
-            ui.add(egui::Slider::new(&mut self.options.dirt_penalty, 1..=50).text("Dirt Penalty"));
-            ui.add(egui::Slider::new(&mut self.options.rage, 0..=100).text("Ant rage"));
+            world_options_ui(ui, &mut self.options);
+            team_options_ui(ui, &mut self.options);
+            for team in export.teams().iter() {
+                ui.collapsing(format!("{} options", team.name), |ui| {
+                    let mut custom = self.team_options.contains_key(&team.id);
+                    if ui
+                        .checkbox(&mut custom, "Override global options")
+                        .changed()
+                    {
+                        if custom {
+                            self.team_options.insert(team.id, self.options);
+                        } else {
+                            self.team_options.remove(&team.id);
+                        }
+                    }
+                    if let Some(options) = self.team_options.get_mut(&team.id) {
+                        team_options_ui(ui, options);
+                    }
+                });
+            }
+
+            ui.collapsing("Disease", |ui| {
+                for team in export.teams().iter() {
+                    let history = self.runner.grid.sir().get(&team.id);
+                    sir_plot(ui, team, history.map_or(&[], |h| h.as_slice()));
+                }
+            });
+
+            ui.collapsing("Deliveries", |ui| {
+                for team in export.teams().iter() {
+                    let d = self.runner.grid.deliveries(team);
+                    ui.label(format!(
+                        "{}: {} direct, {} relayed, {} handoffs",
+                        team.name, d.direct, d.relayed, d.handoffs
+                    ));
+                }
+            });
+            ui.collapsing("Combat", |ui| {
+                for team in export.teams().iter() {
+                    let kills = self.runner.grid.kills(team);
+                    ui.label(format!("{}: {} kills", team.name, kills));
+                }
+            });
+            ui.collapsing("Diplomacy", |ui| {
+                let teams = export.teams();
+                for (idx, a) in teams.iter().enumerate() {
+                    for b in teams.iter().skip(idx + 1) {
+                        let mut relation = self.runner.grid.relation(a, b);
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} / {}", a.name, b.name));
+                            for option in Relation::iter() {
+                                if ui
+                                    .radio_value(&mut relation, option, format!("{:?}", option))
+                                    .changed()
+                                {
+                                    self.runner.set_relation(a.id, b.id, relation);
+                                }
+                            }
+                        });
+                    }
+                }
+            });
 
             // if ui.button("Add food (random)").clicked() {
             //     self.runner.put_food(1);
             // }
+            ui.separator();
+            ui.label("Teams (applied on reset)");
+            let mut removed = None;
+            for (idx, config) in self.roster.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut config.name).desired_width(80.0));
+                    ui.color_edit_button_srgb(&mut config.color.0);
+                    if ui.button("Remove").clicked() {
+                        removed = Some(idx);
+                    }
+                });
+            }
+            if let Some(idx) = removed {
+                self.remove_team(idx);
+            }
+            if ui.button("Add team").clicked() {
+                self.add_team();
+            }
+            ui.separator();
             if ui.button("Reset grid").clicked() {
                 self.reset();
             }
             ui.checkbox(&mut self.profile, "Show profiler");
+            ui.radio_value(&mut self.view, None, "Show all layers");
+            for layer in 0..export.layers() {
+                let name = match layer {
+                    0 => "Show surface".to_string(),
+                    _ => format!("Show layer {}", layer),
+                };
+                ui.radio_value(&mut self.view, Some(layer), name);
+            }
             ui.radio_value(&mut self.add_mode, FOOD_MODE, "Add Food");
-            ui.radio_value(&mut self.add_mode, DIRT_MODE, "Add Dirt");
+            ui.add(egui::Slider::new(&mut self.food_size, 1..=100).text("Placed food size"));
+            ui.horizontal(|ui| {
+                for kind in FoodKind::iter() {
+                    ui.radio_value(&mut self.food_kind, kind, format!("{:?}", kind));
+                }
+            });
+            ui.radio_value(
+                &mut self.add_mode,
+                AddMode {
+                    team: None,
+                    selection_mode: SelectionMode::MIDDEN,
+                },
+                "Add Midden",
+            );
+            ui.radio_value(
+                &mut self.add_mode,
+                AddMode {
+                    team: None,
+                    selection_mode: SelectionMode::WATER,
+                },
+                "Add Water",
+            );
+            ui.radio_value(
+                &mut self.add_mode,
+                AddMode {
+                    team: None,
+                    selection_mode: SelectionMode::APHID,
+                },
+                "Add Aphid",
+            );
+            for kind in PredatorKind::iter() {
+                ui.radio_value(
+                    &mut self.add_mode,
+                    AddMode {
+                        team: None,
+                        selection_mode: SelectionMode::PREDATOR(kind),
+                    },
+                    format!("Add {:?}", kind),
+                );
+            }
+            for terrain in Terrain::iter() {
+                ui.radio_value(
+                    &mut self.add_mode,
+                    AddMode {
+                        team: None,
+                        selection_mode: SelectionMode::DIRT(terrain),
+                    },
+                    format!("Add {:?}", terrain),
+                );
+            }
             for team in export.teams().iter() {
                 ui.radio_value(
                     &mut self.add_mode,
@@ -189,9 +507,25 @@ impl eframe::App for GUIrunner {
             ui.add(egui::Label::new(format!(
                 "Rounds Per Second: {}",
                 self.timer.fps()
-            )))
+            )));
+            let clock = self.runner.grid.clock();
+            ui.add(egui::Label::new(format!(
+                "{} {}",
+                if clock.is_night() { "Night" } else { "Day" },
+                clock
+                    .season()
+                    .map_or(String::new(), |s| format!("({:?})", s)),
+            )));
+            if let Some(victors) = self.runner.grid.victors() {
+                let names: Vec<&str> = victors.iter().map(|t| &*t.name).collect();
+                ui.add(egui::Label::new(format!("Won by {}", names.join(", "))));
+            }
         });
         self.runner.set_opts(self.options);
+        for team in export.teams().iter() {
+            self.runner
+                .set_team_opts(team.id, self.team_options.get(&team.id).copied());
+        }
         self.timer.set(export.frames());
         egui::Window::new("Ant Simulation")
             .collapsible(false)
@@ -200,11 +534,14 @@ impl eframe::App for GUIrunner {
             .constrain(true)
             .frame(Frame::none())
             .show(&ctx, |ui| {
+                if self.view.map_or(false, |layer| layer >= export.layers()) {
+                    self.view = None;
+                }
                 self.texture
-                    .set(export.to_image(), TextureOptions::default());
+                    .set(export.to_image(self.view), TextureOptions::default());
                 let rect = ui.available_size();
                 let y = rect.y as usize;
-                let x = rect.x as usize;
+                let x = rect.x as usize / export.layers();
                 if y != self.rows || x != self.cols {
                     self.rows = y;
                     self.cols = x;
@@ -221,7 +558,7 @@ impl eframe::App for GUIrunner {
     }
 }
 
-pub fn run_gui(rows: usize, cols: usize) -> Result<(), eframe::Error> {
+pub fn run_gui(rows: usize, cols: usize, roster: Vec<TeamConfig>) -> Result<(), eframe::Error> {
     let mut native_options = eframe::NativeOptions::default();
     native_options.fullscreen = true;
     native_options.renderer = Renderer::Wgpu;
@@ -229,6 +566,6 @@ pub fn run_gui(rows: usize, cols: usize) -> Result<(), eframe::Error> {
     return eframe::run_native(
         "My egui App",
         native_options,
-        Box::new(move |cc| Box::new(GUIrunner::new(rows, cols, cc))),
+        Box::new(move |cc| Box::new(GUIrunner::new(rows, cols, roster, cc))),
     );
 }
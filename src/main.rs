@@ -1,21 +1,27 @@
 #![feature(drain_filter)]
 #![feature(hash_drain_filter)]
 
-use std::env;
+use std::{env, process};
 
 use gui::gui_runner::run_gui;
 use tui::run_tui;
 
+use crate::core::TeamConfig;
+
 mod core;
 mod gui;
 mod tui;
 
 fn main() -> Result<(), eframe::Error> {
     let args: Vec<String> = env::args().collect();
+    let roster = TeamConfig::from_args(&args).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
     return if args.len() > 0 && args.get(1).map_or(false, |f| f == "--tui") {
         run_tui();
         Ok(())
     } else {
-        run_gui(500, 1000)
+        run_gui(500, 1000, roster)
     };
 }
@@ -1,5 +1,6 @@
 use crate::core::BaseRunner;
 use crate::core::Options;
+use crate::core::TeamConfig;
 
 pub(crate) fn run_tui() {
     let _num_ants = 0;
@@ -8,7 +9,7 @@ pub(crate) fn run_tui() {
     let rows = 20;
     let _num_rounds = 100;
     let _interval = 5;
-    let _runner = BaseRunner::new(rows, cols, Options::default());
+    let _runner = BaseRunner::new(rows, cols, Options::default(), TeamConfig::default_roster());
     todo!();
     // runner.put_ants(num_ants);
     // runner.put_food(num_food);